@@ -26,6 +26,51 @@ impl Default for DisplayConfig {
             show_colors_label: false,
             disable_startup_message: true,
             field_colors,
+            templates: TemplateConfig::default(),
+            icons: IconConfig::default(),
+            bars: BarConfig::default(),
+            labels: HashMap::new(),
+            plain_colors: PlainColors::Hide,
+        }
+    }
+}
+
+impl Default for TemplateConfig {
+    fn default() -> Self {
+        Self {
+            line: "{label}{sep} {value}".to_string(),
+            header: "{userhost}".to_string(),
+            underline: "-".to_string(),
+            separator: ":".to_string(),
+            fields: HashMap::new(),
+        }
+    }
+}
+
+impl Default for IconConfig {
+    fn default() -> Self {
+        Self {
+            mode: IconMode::None,
+            placement: IconPlacement::Alongside,
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl Default for BarConfig {
+    fn default() -> Self {
+        Self {
+            mode: BarMode::Value,
+            fields: Vec::new(),
+            width: 10,
+            fill: "█".to_string(),
+            empty: "░".to_string(),
+            warning: 60.0,
+            critical: 85.0,
+            normal_color: "green".to_string(),
+            warning_color: "yellow".to_string(),
+            critical_color: "red".to_string(),
+            empty_color: "bright_black".to_string(),
         }
     }
 }
@@ -55,6 +100,7 @@ impl Default for InfoConfig {
 
         Self {
             fields,
+            sections: Vec::new(),
             custom_commands,
         }
     }
@@ -67,12 +113,82 @@ impl Default for AsciiArtConfig {
             path: None,
             builtin: None,
             auto_detect: true,
+            size: None,
+        }
+    }
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            font_family: "'DejaVu Sans Mono', Menlo, Consolas, monospace".to_string(),
+            font: None,
+            font_size: 14.0,
+            background: "#1d1f21".to_string(),
+            foreground: "#c5c8c6".to_string(),
+            padding: 20,
+        }
+    }
+}
+
+impl Default for CpuConfig {
+    fn default() -> Self {
+        Self {
+            sample_ms: 250,
+            per_core: false,
+            brand_remove: ["(R)", "(r)", "(TM)", "(tm)", " CPU", " Processor"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            brand_strip_suffix: true,
+            cgroup_limits: false,
+        }
+    }
+}
+
+impl Default for DiskConfig {
+    fn default() -> Self {
+        Self {
+            include_mounts: Vec::new(),
+            exclude_mounts: Vec::new(),
+            include_fstypes: Vec::new(),
+            exclude_fstypes: DEFAULT_EXCLUDED_FSTYPES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            show_removable: true,
+            dedupe: true,
+            format: DiskFormat::Usage,
+        }
+    }
+}
+
+impl Default for UnitsConfig {
+    fn default() -> Self {
+        Self {
+            system: UnitSystem::Iec,
+            precision: 1,
+            unit: None,
         }
     }
 }
 
+impl Default for DateTimeConfig {
+    fn default() -> Self {
+        Self {
+            format: "%Y-%m-%d %H:%M".to_string(),
+            boot_format: "%Y-%m-%d %H:%M".to_string(),
+        }
+    }
+}
+
+/// Virtual and read-only image filesystems hidden from the `disk` field
+const DEFAULT_EXCLUDED_FSTYPES: &[&str] = &[
+    "tmpfs", "devtmpfs", "ramfs", "overlay", "squashfs", "efivarfs", "autofs", "devfs", "nullfs",
+];
+
 pub fn default_config_toml() -> &'static str {
-    r#"# Rice system information display configuration
+    r##"# Rice system information display configuration
 
 [display]
 # Show ASCII art logo alongside system information
@@ -87,10 +203,15 @@ show_colors_label = false
 # Disable the random startup message in logs (default: true)
 disable_startup_message = true
 
+# What to do with the "colors" field when colors are off (NO_COLOR, --color never,
+# or output piped to a file): "hide" leaves it out, "text" lists the color names
+plain_colors = "hide"
+
 # Customize colors for individual field values
 # Available colors: black, red, green, yellow, blue, magenta, cyan, white
 # Bright variants: bright_black, bright_red, bright_green, bright_yellow, bright_blue, bright_magenta, bright_cyan, bright_white
 [display.field_colors]
+# Section titles use the "title" color (default: bright_white)
 userhost = "bright_green"
 os = "green"
 hostname = "bright_cyan"
@@ -106,6 +227,58 @@ cpu = "bright_green"
 memory = "bright_blue"
 disk = "bright_red"
 
+# Templates control how each info line is laid out.
+# Placeholders: {label}, {sep}, {value}, {field} and any collected value by
+# name, including parts such as {memory.used}, {memory.total}, {memory.percent},
+# {disk.used}, {cpu.brand}, {cpu.cores}, {os.name}, {userhost.user}, {uptime.seconds}.
+# Format specs follow Rust syntax: {label:>10} right-aligns to 10 columns,
+# {label:.<12} pads with dots, {memory.percent:.0} rounds to 0 decimals.
+# Text in [...] is only shown when all placeholders inside it have values.
+[display.templates]
+line = "{label}{sep} {value}"
+header = "{userhost}"
+# Character repeated under the header; set to "" to disable the separator line
+underline = "-"
+separator = ":"
+
+# Per-field templates override the line template for that field
+[display.templates.fields]
+# memory = "{label}{sep} {memory.used} of {memory.total}[ ({memory.percent:.0}%)]"
+
+# Custom labels, overriding the built-in field names
+[display.labels]
+# cpu = "Processor"
+# memory = "RAM"
+
+# Field icons: "none", "nerd" (requires a Nerd Font) or "emoji".
+# Icons are turned off automatically on terminals that can't render them.
+[display.icons]
+mode = "none"
+# "alongside" shows the icon before the label, "replace" shows it instead of the label
+placement = "alongside"
+
+# Override the icon for individual fields
+[display.icons.overrides]
+# cpu = "🔥"
+
+# Usage bars for percentage fields such as memory, disk and battery.
+# mode: "value" (no bar), "bar" (bar instead of the value) or "both".
+# Templates can also place the bar explicitly with {bar}.
+[display.bars]
+mode = "value"
+# Fields to draw bars for; empty means every percentage field
+fields = []
+width = 10
+fill = "█"
+empty = "░"
+# Usage thresholds (percent) for switching to the warning and critical colors
+warning = 60.0
+critical = 85.0
+normal_color = "green"
+warning_color = "yellow"
+critical_color = "red"
+empty_color = "bright_black"
+
 [info]
 # Fields to display in order - remove any you don't want to see
 fields = [
@@ -126,6 +299,27 @@ fields = [
     "colors"
 ]
 
+# Other built-in fields: battery, temperature, cpu_cache, cpu_usage, load, swap, memory_details,
+# session, theme, icons, cursor, font, virtualization, init, failed_units, locale, timezone,
+# users, boot_time, datetime
+
+# Besides field names, entries can be:
+#   ""             a blank spacer line
+#   "---"          a separator line
+#   "title:Text"   a section title
+#   "text:Text"    a literal line, which may reference values like "Up {uptime}"
+#                  (left out when none of those values were collected)
+#
+# Sections are shown after `fields` and grouped under a title, for example:
+# [[info.sections]]
+# title = "Hardware"
+# separator = true
+# fields = ["cpu", "memory", "disk"]
+#
+# [[info.sections]]
+# title = "Software"
+# fields = ["os", "kernel", "shell", "packages"]
+
 # Custom commands to execute and display
 # Each command will be executed and its output displayed as a field
 [info.custom_commands]
@@ -148,7 +342,74 @@ auto_detect = true
 # path = "/path/to/custom/ascii/art.txt"
 # path = "/path/to/image.png"  # For source = "image"
 
-# Force specific builtin logo (not yet implemented)
+# Logo to use when source = "builtin". Run `rice logos` to list available names.
+# Logos placed in ~/.config/rice/ascii_art/ (*.txt or images) are picked up by
+# file name and override built-in logos with the same name.
 # builtin = "arch"
-"#
+
+# Preferred logo size variant, as declared in the logo's sidecar metadata
+# file (e.g. ~/.config/rice/ascii_art/arch.toml):
+#   colors = ["bright_blue", "white"]   # palette, selected with ${c1}, ${c2}, ...
+#   [variants]
+#   small = "arch_small.txt"
+# size = "small"
+
+[cpu]
+# How long to sample CPU usage for the cpu_usage field and `rice cpu`, in milliseconds
+sample_ms = 250
+# Show per-core usage as a sparkline after the overall percentage
+per_core = false
+# Text removed from the CPU brand string
+brand_remove = ["(R)", "(r)", "(TM)", "(tm)", " CPU", " Processor"]
+# Drop suffixes like "CPU @ 2.40GHz" and "8-Core Processor" from the brand string
+brand_strip_suffix = true
+# Show the CPU quota of the container rice runs in, e.g. "limited to 2 CPUs"
+cgroup_limits = false
+
+[memory]
+# Inside a container with a memory limit, show the limit and the container's
+# usage instead of the host's memory
+cgroup_limits = false
+
+[disk]
+# The disk field shows one line per mount, e.g. "Disk (/)" and "Disk (/home)".
+# Single mounts can also be listed as fields, e.g. "disk:/home".
+# Mount points to show (empty shows all) or hide; a trailing * matches a prefix
+include_mounts = []
+exclude_mounts = []
+# Filesystem types to show (empty shows all) or hide
+include_fstypes = []
+exclude_fstypes = ["tmpfs", "devtmpfs", "ramfs", "overlay", "squashfs", "efivarfs", "autofs", "devfs", "nullfs"]
+# Show removable drives such as USB sticks
+show_removable = true
+# Show each device once, at its shortest mount point (hides bind mounts)
+dedupe = true
+# Value format: "usage" (used / total (percent)), "used", "free" or "percent"
+format = "usage"
+
+[units]
+# Byte sizes in memory, swap, disk and network values.
+# "iec" uses powers of 1024 (KiB, MiB, GiB), "si" powers of 1000 (kB, MB, GB)
+system = "iec"
+# Decimal places
+precision = 1
+# Always use one unit, e.g. "GiB" or "MB", instead of picking one per value
+# unit = "GiB"
+
+[datetime]
+# strftime formats for the datetime and boot_time fields, e.g. "%A %d %B %H:%M"
+format = "%Y-%m-%d %H:%M"
+boot_format = "%Y-%m-%d %H:%M"
+
+[export]
+# Appearance of `rice --export out.svg` / `out.png` screenshots
+font_family = "'DejaVu Sans Mono', Menlo, Consolas, monospace"
+font_size = 14.0
+background = "#1d1f21"
+foreground = "#c5c8c6"
+padding = 20
+
+# Font file for PNG exports; common system monospace fonts are tried if unset
+# font = "/usr/share/fonts/truetype/dejavu/DejaVuSansMono.ttf"
+"##
 }
@@ -60,11 +60,22 @@ fn create_default_config(path: &PathBuf) -> Result<()> {
         .with_context(|| format!("Failed to create default config file: {}", path.display()))?;
 
     tracing::info!("Created default config file at: {}", path.display());
+
+    // Give first-time users somewhere to drop their own logos
+    let ascii_art_dir = get_ascii_art_dir()?;
+    fs::create_dir_all(&ascii_art_dir).with_context(|| {
+        format!(
+            "Failed to create ASCII art directory: {}",
+            ascii_art_dir.display()
+        )
+    })?;
+
     Ok(())
 }
 
-pub fn _get_ascii_art_dir() -> Result<PathBuf> {
-    let config_dir = if cfg!(windows) {
+/// Where user logos live. The directory isn't created here and may not exist.
+pub fn get_ascii_art_dir() -> Result<PathBuf> {
+    let ascii_art_dir = if cfg!(windows) {
         dirs::data_dir()
             .context("Could not determine data directory")?
             .join("rice")
@@ -76,12 +87,5 @@ pub fn _get_ascii_art_dir() -> Result<PathBuf> {
     }
     .join("ascii_art");
 
-    fs::create_dir_all(&config_dir).with_context(|| {
-        format!(
-            "Failed to create ASCII art directory: {}",
-            config_dir.display()
-        )
-    })?;
-
-    Ok(config_dir)
+    Ok(ascii_art_dir)
 }
@@ -9,6 +9,18 @@ pub struct Config {
     pub display: DisplayConfig,
     pub info: InfoConfig,
     pub ascii_art: AsciiArtConfig,
+    #[serde(default)]
+    pub export: ExportConfig,
+    #[serde(default)]
+    pub cpu: CpuConfig,
+    #[serde(default)]
+    pub memory: MemoryConfig,
+    #[serde(default)]
+    pub disk: DiskConfig,
+    #[serde(default)]
+    pub units: UnitsConfig,
+    #[serde(default)]
+    pub datetime: DateTimeConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -18,21 +30,119 @@ pub struct DisplayConfig {
     pub show_colors_label: bool,
     pub disable_startup_message: bool,
     pub field_colors: HashMap<String, String>,
+    #[serde(default)]
+    pub templates: TemplateConfig,
+    #[serde(default)]
+    pub icons: IconConfig,
+    #[serde(default)]
+    pub bars: BarConfig,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    #[serde(default)]
+    pub plain_colors: PlainColors,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum PlainColors {
+    #[serde(rename = "hide")]
+    #[default]
+    Hide,
+    #[serde(rename = "text")]
+    Text,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TemplateConfig {
+    pub line: String,
+    pub header: String,
+    pub underline: String,
+    pub separator: String,
+    pub fields: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct IconConfig {
+    pub mode: IconMode,
+    pub placement: IconPlacement,
+    pub overrides: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum IconMode {
+    #[serde(rename = "none")]
+    #[default]
+    None,
+    #[serde(rename = "nerd")]
+    Nerd,
+    #[serde(rename = "emoji")]
+    Emoji,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum IconPlacement {
+    #[serde(rename = "alongside")]
+    #[default]
+    Alongside,
+    #[serde(rename = "replace")]
+    Replace,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BarConfig {
+    pub mode: BarMode,
+    pub fields: Vec<String>,
+    pub width: usize,
+    pub fill: String,
+    pub empty: String,
+    pub warning: f64,
+    pub critical: f64,
+    pub normal_color: String,
+    pub warning_color: String,
+    pub critical_color: String,
+    pub empty_color: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum BarMode {
+    #[serde(rename = "value")]
+    #[default]
+    Value,
+    #[serde(rename = "bar")]
+    Bar,
+    #[serde(rename = "both")]
+    Both,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct InfoConfig {
+    #[serde(default)]
     pub fields: Vec<String>,
     #[serde(default)]
+    pub sections: Vec<SectionConfig>,
+    #[serde(default)]
     pub custom_commands: HashMap<String, String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SectionConfig {
+    pub title: Option<String>,
+    #[serde(default)]
+    pub separator: bool,
+    #[serde(default)]
+    pub fields: Vec<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AsciiArtConfig {
     pub source: AsciiArtSource,
     pub path: Option<String>,
     pub builtin: Option<String>,
     pub auto_detect: bool,
+    #[serde(default)]
+    pub size: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -55,3 +165,133 @@ impl Config {
         loader::load_config_from_path(path)
     }
 }
+
+/// Appearance of `--export` screenshots
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ExportConfig {
+    /// CSS font-family used in SVG exports
+    pub font_family: String,
+    /// TrueType/OpenType font file used for PNG exports
+    pub font: Option<String>,
+    pub font_size: f32,
+    pub background: String,
+    pub foreground: String,
+    pub padding: u32,
+}
+
+/// CPU usage sampling
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CpuConfig {
+    /// Time between the two usage samples in milliseconds. Longer windows
+    /// smooth out spikes; values below the platform minimum are raised to it.
+    pub sample_ms: u64,
+    /// Add a per-core usage sparkline to the `cpu_usage` field
+    pub per_core: bool,
+    /// Text removed from the CPU brand string, e.g. "(R)"
+    pub brand_remove: Vec<String>,
+    /// Drop clock speed and core count suffixes such as "CPU @ 2.40GHz" and
+    /// "8-Core Processor" from the brand string
+    pub brand_strip_suffix: bool,
+    /// Mention the container's CPU quota from its cgroup (`cpu.max`)
+    pub cgroup_limits: bool,
+}
+
+/// Memory reporting
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MemoryConfig {
+    /// Report the cgroup's memory limit and usage (`memory.max`) instead of
+    /// the host's totals when a limit is set, as inside containers
+    pub cgroup_limits: bool,
+}
+
+/// Which mounts the `disk` field shows and how
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DiskConfig {
+    /// Only show these mount points; a trailing `*` matches a prefix
+    pub include_mounts: Vec<String>,
+    pub exclude_mounts: Vec<String>,
+    /// Only show these filesystem types
+    pub include_fstypes: Vec<String>,
+    pub exclude_fstypes: Vec<String>,
+    pub show_removable: bool,
+    /// Show each device once, at its shortest mount point
+    pub dedupe: bool,
+    pub format: DiskFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum DiskFormat {
+    /// "173.4 GiB / 252.3 GiB (68.7%)"
+    #[serde(rename = "usage")]
+    #[default]
+    Usage,
+    /// "173.4 GiB used of 252.3 GiB"
+    #[serde(rename = "used")]
+    Used,
+    /// "78.9 GiB free of 252.3 GiB"
+    #[serde(rename = "free")]
+    Free,
+    /// "68.7%"
+    #[serde(rename = "percent")]
+    Percent,
+}
+
+/// strftime formats for the `datetime` and `boot_time` fields
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DateTimeConfig {
+    pub format: String,
+    pub boot_format: String,
+}
+
+/// How byte sizes are shown in memory, swap, disk and network values
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct UnitsConfig {
+    pub system: UnitSystem,
+    /// Decimal places
+    pub precision: usize,
+    /// Always use this unit instead of picking one per value
+    pub unit: Option<Unit>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum UnitSystem {
+    /// Powers of 1024: KiB, MiB, GiB
+    #[serde(rename = "iec")]
+    #[default]
+    Iec,
+    /// Powers of 1000: kB, MB, GB
+    #[serde(rename = "si")]
+    Si,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum Unit {
+    #[serde(rename = "B")]
+    Byte,
+    #[serde(rename = "KiB")]
+    Kibibyte,
+    #[serde(rename = "MiB")]
+    Mebibyte,
+    #[serde(rename = "GiB")]
+    Gibibyte,
+    #[serde(rename = "TiB")]
+    Tebibyte,
+    #[serde(rename = "PiB")]
+    Pebibyte,
+    #[serde(rename = "kB", alias = "KB")]
+    Kilobyte,
+    #[serde(rename = "MB")]
+    Megabyte,
+    #[serde(rename = "GB")]
+    Gigabyte,
+    #[serde(rename = "TB")]
+    Terabyte,
+    #[serde(rename = "PB")]
+    Petabyte,
+}
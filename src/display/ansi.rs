@@ -0,0 +1,257 @@
+/// An RGB color
+pub type Rgb = (u8, u8, u8);
+
+/// Default palette for the 16 basic ANSI colors
+const PALETTE: [Rgb; 16] = [
+    (0x1d, 0x1f, 0x21),
+    (0xcc, 0x34, 0x2b),
+    (0x19, 0x8c, 0x44),
+    (0xfb, 0xa9, 0x22),
+    (0x39, 0x71, 0xed),
+    (0xa3, 0x6a, 0xc7),
+    (0x3e, 0x99, 0x9f),
+    (0xc5, 0xc8, 0xc6),
+    (0x96, 0x98, 0x96),
+    (0xff, 0x55, 0x55),
+    (0x55, 0xff, 0x55),
+    (0xff, 0xff, 0x55),
+    (0x6b, 0x9b, 0xf5),
+    (0xff, 0x55, 0xff),
+    (0x55, 0xff, 0xff),
+    (0xff, 0xff, 0xff),
+];
+
+/// Text attributes set by SGR escape sequences
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Style {
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+/// A run of text sharing one style
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+}
+
+/// Split colored terminal output into lines of styled spans. Escape sequences
+/// other than colors and text attributes (cursor movement, images) are dropped.
+pub fn parse_lines(text: &str) -> Vec<Vec<Span>> {
+    let mut lines = vec![Vec::new()];
+    let mut style = Style::default();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => {
+                let new_style = match chars.next() {
+                    // CSI: ESC [ params final-byte, only SGR (`m`) changes the style
+                    Some('[') => {
+                        let mut params = String::new();
+                        let mut final_byte = None;
+                        for c in chars.by_ref() {
+                            if ('@'..='~').contains(&c) {
+                                final_byte = Some(c);
+                                break;
+                            }
+                            params.push(c);
+                        }
+                        match final_byte {
+                            Some('m') => apply_sgr(style, &params),
+                            _ => style,
+                        }
+                    }
+                    // OSC, DCS and APC strings end with BEL or ESC \
+                    Some(']' | 'P' | '_') => {
+                        while let Some(c) = chars.next() {
+                            if c == '\x07' || (c == '\x1b' && chars.peek() == Some(&'\\')) {
+                                if c == '\x1b' {
+                                    chars.next();
+                                }
+                                break;
+                            }
+                        }
+                        style
+                    }
+                    _ => style,
+                };
+
+                if new_style != style {
+                    push_span(&mut lines, &mut current, style);
+                    style = new_style;
+                }
+            }
+            '\n' => {
+                push_span(&mut lines, &mut current, style);
+                lines.push(Vec::new());
+            }
+            '\r' => {}
+            c => current.push(c),
+        }
+    }
+
+    push_span(&mut lines, &mut current, style);
+    if lines.len() > 1 && lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Convert colored terminal output to HTML with inline-styled spans
+pub fn to_html(text: &str) -> String {
+    parse_lines(text)
+        .iter()
+        .map(|line| {
+            line.iter()
+                .map(|span| {
+                    let css = span.style.css();
+                    if css.is_empty() {
+                        escape_html(&span.text)
+                    } else {
+                        format!("<span style=\"{}\">{}</span>", css, escape_html(&span.text))
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Format a color as `#rrggbb`
+pub fn hex(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2)
+}
+
+impl Style {
+    fn css(&self) -> String {
+        let mut css = Vec::new();
+        if let Some(fg) = self.fg {
+            css.push(format!("color:{}", hex(fg)));
+        }
+        if let Some(bg) = self.bg {
+            css.push(format!("background-color:{}", hex(bg)));
+        }
+        if self.bold {
+            css.push("font-weight:bold".to_string());
+        }
+        if self.dim {
+            css.push("opacity:0.7".to_string());
+        }
+        if self.italic {
+            css.push("font-style:italic".to_string());
+        }
+        if self.underline {
+            css.push("text-decoration:underline".to_string());
+        }
+        css.join(";")
+    }
+}
+
+fn push_span(lines: &mut [Vec<Span>], text: &mut String, style: Style) {
+    if text.is_empty() {
+        return;
+    }
+    if let Some(line) = lines.last_mut() {
+        line.push(Span {
+            text: std::mem::take(text),
+            style,
+        });
+    }
+}
+
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    let codes: Vec<u8> = params
+        .split(';')
+        .map(|code| code.parse().unwrap_or(0))
+        .collect();
+    let mut codes = codes.into_iter();
+
+    while let Some(code) = codes.next() {
+        match code {
+            0 => style = Style::default(),
+            1 => style.bold = true,
+            2 => style.dim = true,
+            3 => style.italic = true,
+            4 => style.underline = true,
+            22 => {
+                style.bold = false;
+                style.dim = false;
+            }
+            23 => style.italic = false,
+            24 => style.underline = false,
+            30..=37 => style.fg = Some(PALETTE[(code - 30) as usize]),
+            90..=97 => style.fg = Some(PALETTE[(code - 90 + 8) as usize]),
+            40..=47 => style.bg = Some(PALETTE[(code - 40) as usize]),
+            100..=107 => style.bg = Some(PALETTE[(code - 100 + 8) as usize]),
+            38 => style.fg = extended_color(&mut codes),
+            48 => style.bg = extended_color(&mut codes),
+            39 => style.fg = None,
+            49 => style.bg = None,
+            _ => {}
+        }
+    }
+
+    style
+}
+
+/// Read the rest of a `38;5;n` or `38;2;r;g;b` color
+fn extended_color(codes: &mut impl Iterator<Item = u8>) -> Option<Rgb> {
+    match codes.next()? {
+        5 => Some(color_256(codes.next()?)),
+        2 => Some((codes.next()?, codes.next()?, codes.next()?)),
+        _ => None,
+    }
+}
+
+fn color_256(index: u8) -> Rgb {
+    match index {
+        0..=15 => PALETTE[index as usize],
+        16..=231 => {
+            let index = index - 16;
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            (level(index / 36), level((index / 6) % 6), level(index % 6))
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        let lines = parse_lines("\x1b[1;36mOS\x1b[0m: Linux\n\x1b[38;5;196mred\x1b[0m\x1b[2A");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0][0].text, "OS");
+        assert!(lines[0][0].style.bold);
+        assert_eq!(lines[0][0].style.fg, Some(PALETTE[6]));
+        assert_eq!(lines[0][1].text, ": Linux");
+        assert_eq!(lines[0][1].style, Style::default());
+        assert_eq!(lines[1][0].style.fg, Some((255, 0, 0)));
+    }
+
+    #[test]
+    fn test_to_html() {
+        assert_eq!(
+            to_html("\x1b[31m<red>\x1b[0m & plain"),
+            "<span style=\"color:#cc342b\">&lt;red&gt;</span> &amp; plain"
+        );
+    }
+}
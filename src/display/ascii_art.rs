@@ -1,86 +1,170 @@
+use super::logos::{
+    builtin_art, load_logo_meta, LogoEntry, LogoRegistry, LogoSource, DEFAULT_LOGO_COLOR,
+};
 use crate::config::{AsciiArtSource, Config};
+use crate::utils::terminal;
 use anyhow::{Context, Result};
-use std::collections::HashMap;
 use std::fs;
+use std::path::{Path, PathBuf};
 
-pub fn get_ascii_art(config: &Config) -> Result<String> {
-    match config.ascii_art.source {
-        AsciiArtSource::None => Ok(String::new()),
+/// A logo ready for display along with the palette used to color it
+pub struct Logo {
+    pub art: String,
+    pub colors: Vec<String>,
+}
+
+impl Logo {
+    fn plain(art: String) -> Self {
+        Self {
+            art,
+            colors: vec![DEFAULT_LOGO_COLOR.to_string()],
+        }
+    }
+}
+
+pub fn get_ascii_art(config: &Config) -> Result<Logo> {
+    match select_logo(config)? {
+        Some((source, colors)) => render_logo(&source, colors, terminal::colors_enabled()),
+        None => Ok(Logo::plain(String::new())),
+    }
+}
+
+/// The image file the configured logo resolves to, if it is an image. This
+/// covers image logos picked by name from the user's logo directory as well as
+/// `source = "image"`.
+pub fn logo_image_path(config: &Config) -> Option<PathBuf> {
+    match select_logo(config) {
+        Ok(Some((LogoSource::Image(path), _))) => Some(path),
+        _ => None,
+    }
+}
+
+/// Resolve the configured logo to its size variant and palette, or `None` when
+/// logos are turned off
+fn select_logo(config: &Config) -> Result<Option<(LogoSource, Vec<String>)>> {
+    let size = config.ascii_art.size.as_deref();
+
+    let logo = match config.ascii_art.source {
+        AsciiArtSource::None => return Ok(None),
         AsciiArtSource::Auto => {
-            if config.ascii_art.auto_detect {
-                get_auto_detected_art()
+            let name = if config.ascii_art.auto_detect {
+                detect_logo_name()
             } else {
-                get_builtin_art("default")
-            }
+                "default"
+            };
+            named_logo(name, size)?
         }
         AsciiArtSource::Builtin => {
             let name = config.ascii_art.builtin.as_deref().unwrap_or("default");
-            get_builtin_art(name)
+            named_logo(name, size)?
         }
         AsciiArtSource::File => {
             if let Some(path) = &config.ascii_art.path {
-                load_ascii_art_file(path)
+                file_logo(Path::new(path), size, LogoSource::Text)
             } else {
-                get_builtin_art("default")
+                named_logo("default", size)?
             }
         }
         AsciiArtSource::Image => {
             if let Some(path) = &config.ascii_art.path {
-                render_image_as_terminal(path)
+                file_logo(Path::new(path), size, LogoSource::Image)
             } else {
-                get_builtin_art("default")
+                named_logo("default", size)?
             }
         }
-    }
+    };
+
+    Ok(Some(logo))
 }
 
-fn get_auto_detected_art() -> Result<String> {
-    // Try to detect OS and return appropriate ASCII art
+fn detect_logo_name() -> &'static str {
+    // Try to detect OS and return the matching logo name
     let os_name = sysinfo::System::name().unwrap_or_default().to_lowercase();
 
     if os_name.contains("mac") || os_name.contains("darwin") {
-        get_builtin_art("macos")
+        "macos"
     } else if os_name.contains("ubuntu") {
-        get_builtin_art("ubuntu")
+        "ubuntu"
     } else if os_name.contains("arch") {
-        get_builtin_art("arch")
+        "arch"
     } else if os_name.contains("debian") {
-        get_builtin_art("debian")
+        "debian"
     } else if os_name.contains("fedora") {
-        get_builtin_art("fedora")
+        "fedora"
     } else if os_name.contains("linux") {
-        get_builtin_art("linux")
+        "linux"
     } else {
-        get_builtin_art("default")
+        "default"
     }
 }
 
-fn get_builtin_art(name: &str) -> Result<String> {
-    let art_map = get_builtin_art_map();
+fn named_logo(name: &str, size: Option<&str>) -> Result<(LogoSource, Vec<String>)> {
+    let registry = LogoRegistry::load();
+
+    let entry = match registry.get(name) {
+        Some(entry) => entry,
+        None => {
+            tracing::warn!("Unknown logo '{}', using default", name);
+            registry.get("default").context("No ASCII art found")?
+        }
+    };
+
+    Ok((entry.variant(size), entry.colors()))
+}
+
+fn file_logo(
+    path: &Path,
+    size: Option<&str>,
+    source: fn(PathBuf) -> LogoSource,
+) -> (LogoSource, Vec<String>) {
+    let entry = LogoEntry {
+        name: path.display().to_string(),
+        source: source(path.to_path_buf()),
+        meta: load_logo_meta(path),
+    };
+
+    (entry.variant(size), entry.colors())
+}
 
-    art_map
-        .get(name)
-        .or_else(|| art_map.get("default"))
-        .map(|s| s.to_string())
-        .context("No ASCII art found")
+fn render_logo(source: &LogoSource, colors: Vec<String>, colors_enabled: bool) -> Result<Logo> {
+    let art = match source {
+        LogoSource::Builtin(art) => art.to_string(),
+        LogoSource::Text(path) => load_ascii_art_file(path)?,
+        // Images are drawn with escape sequences, so plain output uses the
+        // built-in OS logo. User logos aren't considered, since one saved
+        // under the OS name could be this very image.
+        LogoSource::Image(_) if !colors_enabled => {
+            let art = builtin_art(detect_logo_name())
+                .or_else(|| builtin_art("default"))
+                .context("No ASCII art found")?;
+            return Ok(Logo::plain(art.to_string()));
+        }
+        LogoSource::Image(path) => render_image_as_terminal(&path.to_string_lossy())?,
+    };
+    Ok(Logo { art, colors })
 }
 
-fn load_ascii_art_file(path: &str) -> Result<String> {
-    fs::read_to_string(path).with_context(|| format!("Failed to read ASCII art file: {}", path))
+fn load_ascii_art_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path)
+        .with_context(|| format!("Failed to read ASCII art file: {}", path.display()))
 }
 
-fn get_builtin_art_map() -> HashMap<&'static str, &'static str> {
-    let mut map = HashMap::new();
+/// Print every logo that can be selected through `ascii_art.builtin`
+pub fn list_logos() {
+    let registry = LogoRegistry::load();
+
+    for entry in registry.iter() {
+        let origin = if entry.is_user() { "user" } else { "builtin" };
+        let mut line = format!("{:<16} {:<8} {:<6}", entry.name, origin, entry.kind());
 
-    map.insert("default", include_str!("../assets/ascii/default.txt"));
-    map.insert("macos", include_str!("../assets/ascii/macos.txt"));
-    map.insert("linux", include_str!("../assets/ascii/linux.txt"));
-    map.insert("ubuntu", include_str!("../assets/ascii/ubuntu.txt"));
-    map.insert("arch", include_str!("../assets/ascii/arch.txt"));
-    map.insert("debian", include_str!("../assets/ascii/debian.txt"));
-    map.insert("fedora", include_str!("../assets/ascii/fedora.txt"));
+        if !entry.meta.variants.is_empty() {
+            let mut sizes: Vec<&str> = entry.meta.variants.keys().map(|s| s.as_str()).collect();
+            sizes.sort();
+            line.push_str(&format!(" sizes: {}", sizes.join(", ")));
+        }
 
-    map
+        println!("{}", line.trim_end());
+    }
 }
 
 #[cfg(feature = "images")]
@@ -201,3 +285,37 @@ fn render_image_as_ascii_blocks(path: &str) -> Result<String> {
 fn render_image_as_terminal(_path: &str) -> Result<String> {
     anyhow::bail!("Image display not supported. Please compile with --features images")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_image_logo_uses_builtin_art_and_default_palette() {
+        let source = LogoSource::Image(PathBuf::from("/nonexistent/debian.png"));
+        let logo = render_logo(&source, vec!["red".to_string()], false).unwrap();
+        let expected = builtin_art(detect_logo_name()).or_else(|| builtin_art("default"));
+        assert_eq!(Some(logo.art.as_str()), expected);
+        assert_eq!(logo.colors, vec![DEFAULT_LOGO_COLOR.to_string()]);
+    }
+
+    #[test]
+    fn image_variant_of_text_logo_is_an_image_logo() {
+        let mut config = Config::default();
+        config.ascii_art.source = AsciiArtSource::File;
+        config.ascii_art.path = Some("/logos/arch.txt".to_string());
+        assert_eq!(logo_image_path(&config), None);
+
+        let dir = std::env::temp_dir().join(format!("rice-ascii-art-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("arch.toml"), "[variants]\nlarge = \"arch.png\"\n").unwrap();
+        config.ascii_art.path = Some(dir.join("arch.txt").display().to_string());
+        config.ascii_art.size = Some("large".to_string());
+        assert_eq!(logo_image_path(&config), Some(dir.join("arch.png")));
+
+        config.ascii_art.source = AsciiArtSource::None;
+        assert_eq!(logo_image_path(&config), None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,104 @@
+use super::apply_color_by_name;
+use crate::config::BarConfig;
+
+/// Fields where a high percentage is good, so thresholds apply to the remainder
+const HIGHER_IS_BETTER: &[&str] = &["battery"];
+
+/// Plain usage bar for a percentage
+pub fn render_bar(percent: f64, config: &BarConfig) -> String {
+    let (filled, empty) = bar_lengths(percent, config.width);
+    format!(
+        "{}{}",
+        config.fill.repeat(filled),
+        config.empty.repeat(empty)
+    )
+}
+
+/// Usage bar colored by threshold
+pub fn paint_bar(field: &str, percent: f64, config: &BarConfig) -> String {
+    let (filled, empty) = bar_lengths(percent, config.width);
+
+    let load = if HIGHER_IS_BETTER.contains(&field) {
+        100.0 - percent
+    } else {
+        percent
+    };
+    let color = if load >= config.critical {
+        &config.critical_color
+    } else if load >= config.warning {
+        &config.warning_color
+    } else {
+        &config.normal_color
+    };
+
+    format!(
+        "{}{}",
+        apply_color_by_name(&config.fill.repeat(filled), color),
+        apply_color_by_name(&config.empty.repeat(empty), &config.empty_color)
+    )
+}
+
+fn bar_lengths(percent: f64, width: usize) -> (usize, usize) {
+    let filled = ((percent.clamp(0.0, 100.0) / 100.0) * width as f64).round() as usize;
+    (filled, width - filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BarMode, Config};
+    use crate::display::render_info_with_colors;
+    use crate::info::{FieldValue, Info};
+    use crate::utils::terminal::strip_ansi;
+
+    fn bar_config(width: usize) -> BarConfig {
+        BarConfig {
+            width,
+            fill: "#".to_string(),
+            empty: "-".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_bar_width() {
+        assert_eq!(render_bar(50.0, &bar_config(10)), "#####-----");
+        assert_eq!(render_bar(25.0, &bar_config(4)), "#---");
+        assert_eq!(render_bar(33.0, &bar_config(3)), "#--");
+        assert_eq!(render_bar(80.0, &bar_config(0)), "");
+    }
+
+    #[test]
+    fn test_bar_clamps_percent() {
+        assert_eq!(render_bar(-20.0, &bar_config(5)), "-----");
+        assert_eq!(render_bar(150.0, &bar_config(5)), "#####");
+        assert_eq!(
+            strip_ansi(&paint_bar("memory", 250.0, &bar_config(5))),
+            "#####"
+        );
+    }
+
+    #[test]
+    fn test_missing_battery_has_no_bar() {
+        let mut config = Config::default();
+        config.info.fields = vec!["memory".to_string(), "battery".to_string()];
+        config.display.bars = BarConfig {
+            mode: BarMode::Both,
+            ..bar_config(4)
+        };
+
+        let mut info = Info::default();
+        info.insert(
+            "memory",
+            FieldValue::from("4 GiB / 16 GiB".to_string()).with_part("percent", "25.0"),
+        );
+        let output = strip_ansi(&render_info_with_colors(&info, &config).unwrap());
+        assert!(output.contains("4 GiB / 16 GiB #---"));
+        assert!(!output.contains("Battery"));
+
+        // A battery without a percentage shows its value alone
+        info.insert("battery", FieldValue::from("Unknown".to_string()));
+        let output = strip_ansi(&render_info_with_colors(&info, &config).unwrap());
+        assert!(output.lines().any(|line| line.ends_with("Unknown")));
+    }
+}
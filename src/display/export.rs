@@ -0,0 +1,377 @@
+use super::ansi::{self, escape_html, hex, Rgb, Span};
+use super::{ascii_art, render_info_with_colors, report, Display};
+use crate::config::ExportConfig;
+use crate::info::Info;
+use crate::utils::terminal::display_width;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Character cells taken by an image logo, matching the terminal rendering
+const IMAGE_COLUMNS: usize = 30;
+const IMAGE_ROWS: usize = 15;
+const IMAGE_GAP: usize = 5;
+
+/// Rendered output laid out on a character grid
+struct Screen {
+    lines: Vec<Vec<Span>>,
+    /// Image logo drawn left of the text
+    image: Option<PathBuf>,
+}
+
+impl Screen {
+    fn text_column(&self) -> usize {
+        if self.image.is_some() {
+            IMAGE_COLUMNS + IMAGE_GAP
+        } else {
+            0
+        }
+    }
+
+    fn columns(&self) -> usize {
+        let text = self
+            .lines
+            .iter()
+            .map(|line| line.iter().map(|span| display_width(&span.text)).sum())
+            .max()
+            .unwrap_or(0);
+        self.text_column() + text
+    }
+
+    fn rows(&self) -> usize {
+        if self.image.is_some() {
+            self.lines.len().max(IMAGE_ROWS)
+        } else {
+            self.lines.len()
+        }
+    }
+}
+
+/// Render the display to an SVG or PNG file, picked by the file extension
+pub fn export(display: &Display, info: &Info, path: &Path) -> Result<()> {
+    let config = &display.config;
+
+    let image = if config.display.show_logo {
+        ascii_art::logo_image_path(config)
+    } else {
+        None
+    };
+    let text = if image.is_some() {
+        render_info_with_colors(info, config)?
+    } else {
+        display.render(info)?
+    };
+    let screen = Screen {
+        lines: ansi::parse_lines(&text),
+        image,
+    };
+
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    match extension.as_str() {
+        "svg" => fs::write(path, render_svg(&screen, &config.export)?)
+            .with_context(|| format!("Failed to write {}", path.display())),
+        "png" => render_png(&screen, &config.export, path),
+        _ => bail!(
+            "Unsupported export file '{}', use a .svg or .png extension",
+            path.display()
+        ),
+    }
+}
+
+fn render_svg(screen: &Screen, config: &ExportConfig) -> Result<String> {
+    let background = parse_color(&config.background)?;
+    let foreground = parse_color(&config.foreground)?;
+
+    // Typical monospace proportions, since the viewer picks the actual font
+    let cell_width = config.font_size * 0.6;
+    let line_height = config.font_size * 1.2;
+    let padding = config.padding as f32;
+    let width = padding * 2.0 + screen.columns() as f32 * cell_width;
+    let height = padding * 2.0 + screen.rows() as f32 * line_height;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        hex(background),
+        w = width.ceil(),
+        h = height.ceil(),
+    );
+
+    if let Some(uri) = screen.image.as_deref().and_then(report::image_data_uri) {
+        svg.push_str(&format!(
+            "<image x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" href=\"{}\"/>\n",
+            padding,
+            padding,
+            IMAGE_COLUMNS as f32 * cell_width,
+            IMAGE_ROWS as f32 * line_height,
+            uri
+        ));
+    }
+
+    svg.push_str(&format!(
+        "<g font-family=\"{}\" font-size=\"{}\" fill=\"{}\" xml:space=\"preserve\">\n",
+        escape_html(&config.font_family),
+        config.font_size,
+        hex(foreground)
+    ));
+
+    for (row, line) in screen.lines.iter().enumerate() {
+        let top = padding + row as f32 * line_height;
+        let mut column = screen.text_column();
+        let mut text = String::new();
+
+        for span in line {
+            let x = padding + column as f32 * cell_width;
+            let columns = display_width(&span.text);
+
+            if let Some(bg) = span.style.bg {
+                svg.push_str(&format!(
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"/>\n",
+                    x,
+                    top,
+                    columns as f32 * cell_width,
+                    line_height,
+                    hex(bg)
+                ));
+            }
+
+            let mut attributes = format!(" x=\"{:.1}\"", x);
+            if let Some(fg) = span.style.fg {
+                attributes.push_str(&format!(" fill=\"{}\"", hex(fg)));
+            }
+            if span.style.bold {
+                attributes.push_str(" font-weight=\"bold\"");
+            }
+            if span.style.dim {
+                attributes.push_str(" fill-opacity=\"0.7\"");
+            }
+            if span.style.italic {
+                attributes.push_str(" font-style=\"italic\"");
+            }
+            if span.style.underline {
+                attributes.push_str(" text-decoration=\"underline\"");
+            }
+            text.push_str(&format!(
+                "<tspan{}>{}</tspan>",
+                attributes,
+                escape_html(&span.text)
+            ));
+
+            column += columns;
+        }
+
+        if !text.is_empty() {
+            svg.push_str(&format!(
+                "<text y=\"{:.1}\">{}</text>\n",
+                top + line_height * 0.8,
+                text
+            ));
+        }
+    }
+
+    svg.push_str("</g>\n</svg>\n");
+    Ok(svg)
+}
+
+/// Font files tried for PNG exports when `export.font` isn't set
+#[cfg(feature = "images")]
+const FONT_CANDIDATES: &[&str] = &[
+    "/usr/share/fonts/truetype/dejavu/DejaVuSansMono.ttf",
+    "/usr/share/fonts/TTF/DejaVuSansMono.ttf",
+    "/usr/share/fonts/dejavu/DejaVuSansMono.ttf",
+    "/usr/share/fonts/dejavu-sans-mono-fonts/DejaVuSansMono.ttf",
+    "/usr/share/fonts/truetype/liberation/LiberationMono-Regular.ttf",
+    "/usr/share/fonts/liberation-mono/LiberationMono-Regular.ttf",
+    "/System/Library/Fonts/Menlo.ttc",
+    "/System/Library/Fonts/Monaco.ttf",
+    "C:\\Windows\\Fonts\\consola.ttf",
+];
+
+#[cfg(feature = "images")]
+fn render_png(screen: &Screen, config: &ExportConfig, path: &Path) -> Result<()> {
+    use ab_glyph::{Font, PxScale, ScaleFont};
+    use image::{imageops, Rgba, RgbaImage};
+    use unicode_width::UnicodeWidthChar;
+
+    let background = parse_color(&config.background)?;
+    let foreground = parse_color(&config.foreground)?;
+    let font = load_font(config)?;
+
+    let scale = PxScale::from(config.font_size);
+    let scaled = font.as_scaled(scale);
+    let cell_width = scaled.h_advance(font.glyph_id('M')).ceil() as u32;
+    let line_height = (scaled.ascent() - scaled.descent() + scaled.line_gap()).ceil() as u32;
+    let padding = config.padding;
+
+    let width = padding * 2 + screen.columns() as u32 * cell_width;
+    let height = padding * 2 + screen.rows() as u32 * line_height;
+    let mut canvas = RgbaImage::from_pixel(
+        width,
+        height,
+        Rgba([background.0, background.1, background.2, 255]),
+    );
+
+    if let Some(image) = &screen.image {
+        match image::open(image) {
+            Ok(logo) => {
+                let logo = logo
+                    .resize(
+                        IMAGE_COLUMNS as u32 * cell_width,
+                        IMAGE_ROWS as u32 * line_height,
+                        imageops::FilterType::Triangle,
+                    )
+                    .to_rgba8();
+                imageops::overlay(&mut canvas, &logo, padding as i64, padding as i64);
+            }
+            Err(e) => tracing::warn!("Failed to load logo image {}: {}", image.display(), e),
+        }
+    }
+
+    for (row, line) in screen.lines.iter().enumerate() {
+        let top = padding + row as u32 * line_height;
+        let baseline = top as f32 + scaled.ascent();
+        let mut x = padding + screen.text_column() as u32 * cell_width;
+
+        for span in line {
+            let mut color = span.style.fg.unwrap_or(foreground);
+            if span.style.dim {
+                color = blend(color, background, 0.7);
+            }
+
+            if let Some(bg) = span.style.bg {
+                let span_width = display_width(&span.text) as u32 * cell_width;
+                for px in x..(x + span_width).min(width) {
+                    for py in top..(top + line_height).min(height) {
+                        canvas.put_pixel(px, py, Rgba([bg.0, bg.1, bg.2, 255]));
+                    }
+                }
+            }
+
+            for c in span.text.chars() {
+                // Bold is faked by drawing the glyph twice, a pixel apart
+                let strokes: &[f32] = if span.style.bold { &[0.0, 1.0] } else { &[0.0] };
+                for offset in strokes {
+                    let glyph = font.glyph_id(c).with_scale_and_position(
+                        scale,
+                        ab_glyph::point(x as f32 + offset, baseline),
+                    );
+                    let Some(outlined) = font.outline_glyph(glyph) else {
+                        continue;
+                    };
+                    let bounds = outlined.px_bounds();
+                    outlined.draw(|gx, gy, coverage| {
+                        let px = bounds.min.x as i64 + gx as i64;
+                        let py = bounds.min.y as i64 + gy as i64;
+                        if px < 0 || py < 0 || px >= width as i64 || py >= height as i64 {
+                            return;
+                        }
+                        let pixel = canvas.get_pixel_mut(px as u32, py as u32);
+                        let under = (pixel[0], pixel[1], pixel[2]);
+                        let (r, g, b) = blend(color, under, coverage.min(1.0));
+                        *pixel = Rgba([r, g, b, 255]);
+                    });
+                }
+                x += c.width().unwrap_or(0) as u32 * cell_width;
+            }
+        }
+    }
+
+    canvas
+        .save(path)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(not(feature = "images"))]
+fn render_png(_screen: &Screen, _config: &ExportConfig, _path: &Path) -> Result<()> {
+    bail!("PNG export not supported. Please compile with --features images")
+}
+
+#[cfg(feature = "images")]
+fn load_font(config: &ExportConfig) -> Result<ab_glyph::FontVec> {
+    let path = match &config.font {
+        Some(font) => PathBuf::from(font),
+        None => FONT_CANDIDATES
+            .iter()
+            .map(PathBuf::from)
+            .find(|path| path.exists())
+            .context("No monospace font found, set export.font to a .ttf file")?,
+    };
+
+    let data =
+        fs::read(&path).with_context(|| format!("Failed to read font: {}", path.display()))?;
+    ab_glyph::FontVec::try_from_vec_and_index(data, 0)
+        .with_context(|| format!("Failed to load font: {}", path.display()))
+}
+
+/// Mix `color` over `under` with the given opacity
+#[cfg(feature = "images")]
+fn blend(color: Rgb, under: Rgb, opacity: f32) -> Rgb {
+    let mix = |top: u8, bottom: u8| {
+        (top as f32 * opacity + bottom as f32 * (1.0 - opacity)).round() as u8
+    };
+    (
+        mix(color.0, under.0),
+        mix(color.1, under.1),
+        mix(color.2, under.2),
+    )
+}
+
+/// Parse a `#rrggbb` color
+fn parse_color(color: &str) -> Result<Rgb> {
+    let digits = color.trim().trim_start_matches('#');
+    let channel = |i: usize| {
+        digits
+            .get(i..i + 2)
+            .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+    };
+
+    match (digits.len(), channel(0), channel(2), channel(4)) {
+        (6, Some(r), Some(g), Some(b)) => Ok((r, g, b)),
+        _ => bail!("Invalid color '{}', expected #rrggbb", color),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn svg(text: &str) -> String {
+        let screen = Screen {
+            lines: ansi::parse_lines(text),
+            image: None,
+        };
+        render_svg(&screen, &ExportConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_svg_escapes_text() {
+        let svg = svg("OS: <Linux> & \"friends\"");
+        assert!(svg.contains(">OS: &lt;Linux&gt; &amp; &quot;friends&quot;</tspan>"));
+        assert!(!svg.contains("<Linux>"));
+    }
+
+    #[test]
+    fn test_svg_colors() {
+        let svg = svg("\x1b[1;38;2;255;0;0mred\x1b[0m \x1b[48;2;0;255;0m  \x1b[0m");
+        assert!(svg.contains("fill=\"#ff0000\" font-weight=\"bold\">red</tspan>"));
+        // Background colors are drawn as cells behind the text
+        assert!(svg.contains(
+            "<rect x=\"53.6\" y=\"20.0\" width=\"16.8\" height=\"16.8\" fill=\"#00ff00\"/>"
+        ));
+        assert!(svg.contains("<rect width=\"100%\" height=\"100%\" fill=\"#1d1f21\"/>"));
+        assert!(!svg.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("#1d1f21").unwrap(), (0x1d, 0x1f, 0x21));
+        assert_eq!(parse_color("C5C8C6").unwrap(), (0xc5, 0xc8, 0xc6));
+        assert!(parse_color("#fff").is_err());
+        assert!(parse_color("#gggggg").is_err());
+    }
+}
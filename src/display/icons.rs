@@ -0,0 +1,162 @@
+use crate::config::{IconConfig, IconMode};
+use crate::utils::terminal;
+
+/// Default (Nerd Font, emoji) icons for built-in fields
+const DEFAULT_ICONS: &[(&str, &str, &str)] = &[
+    ("userhost", "\u{f007}", "👤"),
+    ("os", "\u{f17c}", "🐧"),
+    ("hostname", "\u{f109}", "💻"),
+    ("kernel", "\u{f013}", "🔧"),
+    ("uptime", "\u{f017}", "⏰"),
+    ("packages", "\u{f187}", "📦"),
+    ("shell", "\u{f120}", "🐚"),
+    ("resolution", "\u{f108}", "📐"),
+    ("de", "\u{f2d0}", "🪟"),
+    ("wm", "\u{f2d2}", "🧩"),
+    ("session", "\u{f108}", "🖥️"),
+    ("virtualization", "\u{f1b3}", "📦"),
+    ("init", "\u{f013}", "⚙️"),
+    ("failed_units", "\u{f071}", "⚠️"),
+    ("locale", "\u{f1ab}", "🌐"),
+    ("timezone", "\u{f0ac}", "🌍"),
+    ("users", "\u{f0c0}", "👥"),
+    ("boot_time", "\u{f011}", "⏻"),
+    ("datetime", "\u{f017}", "🕒"),
+    ("theme", "\u{f1fc}", "🖌️"),
+    ("icons", "\u{f03e}", "🖼️"),
+    ("cursor", "\u{f245}", "🖱️"),
+    ("font", "\u{f031}", "🔤"),
+    ("terminal", "\u{f489}", "📟"),
+    ("terminal_font", "\u{f031}", "🔤"),
+    ("cpu", "\u{f4bc}", "🧮"),
+    ("cpu_usage", "\u{f0e4}", "📈"),
+    ("cpu_cache", "\u{f1c0}", "🗄️"),
+    ("load", "\u{f080}", "📊"),
+    ("memory", "\u{f2db}", "🧠"),
+    ("memory_details", "\u{f2db}", "🧠"),
+    ("swap", "\u{f0ec}", "🔄"),
+    ("disk", "\u{f0a0}", "💾"),
+    ("battery", "\u{f240}", "🔋"),
+    ("temperature", "\u{f2c9}", "🌡️"),
+    ("colors", "\u{f53f}", "🎨"),
+];
+
+/// Icon mode in effect, taking terminal capabilities into account
+pub fn effective_mode(config: &IconConfig) -> IconMode {
+    mode_for_terminal(config.mode, terminal::supports_icons())
+}
+
+fn mode_for_terminal(mode: IconMode, supports_icons: bool) -> IconMode {
+    match mode {
+        IconMode::None => IconMode::None,
+        mode if supports_icons => mode,
+        _ => {
+            tracing::debug!("Terminal can't render icons, disabling them");
+            IconMode::None
+        }
+    }
+}
+
+/// Icon for a field, preferring user overrides over the built-in defaults
+pub fn get_field_icon(field: &str, mode: IconMode, config: &IconConfig) -> Option<String> {
+    if mode == IconMode::None {
+        return None;
+    }
+
+    if let Some(icon) = config.overrides.get(field) {
+        return Some(icon.clone());
+    }
+
+    DEFAULT_ICONS
+        .iter()
+        .find(|(name, _, _)| *name == field)
+        .map(|(_, nerd, emoji)| match mode {
+            IconMode::Emoji => emoji.to_string(),
+            _ => nerd.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(overrides: &[(&str, &str)]) -> IconConfig {
+        IconConfig {
+            overrides: overrides
+                .iter()
+                .map(|(field, icon)| (field.to_string(), icon.to_string()))
+                .collect(),
+            ..IconConfig::default()
+        }
+    }
+
+    fn supports_icons(vars: &[(&str, &str)]) -> bool {
+        terminal::supports_icons_with(|name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn test_glyph_for_mode() {
+        let config = config(&[]);
+        assert_eq!(
+            get_field_icon("os", IconMode::Nerd, &config).as_deref(),
+            Some("\u{f17c}")
+        );
+        assert_eq!(
+            get_field_icon("os", IconMode::Emoji, &config).as_deref(),
+            Some("🐧")
+        );
+        assert_eq!(get_field_icon("os", IconMode::None, &config), None);
+        assert_eq!(get_field_icon("unknown", IconMode::Nerd, &config), None);
+    }
+
+    #[test]
+    fn test_overrides_take_precedence() {
+        let config = config(&[("os", "OS>"), ("custom", "*")]);
+        for mode in [IconMode::Nerd, IconMode::Emoji] {
+            assert_eq!(get_field_icon("os", mode, &config).as_deref(), Some("OS>"));
+            assert_eq!(
+                get_field_icon("custom", mode, &config).as_deref(),
+                Some("*")
+            );
+        }
+        // Fields without an override keep the default icon
+        assert_eq!(
+            get_field_icon("kernel", IconMode::Emoji, &config).as_deref(),
+            Some("🔧")
+        );
+        // Overrides don't bring icons back when they're turned off
+        assert_eq!(get_field_icon("os", IconMode::None, &config), None);
+    }
+
+    #[test]
+    fn test_effective_mode_fallback() {
+        let utf8 = [("TERM", "xterm-256color"), ("LANG", "en_US.UTF-8")];
+        assert!(supports_icons(&utf8));
+        assert_eq!(
+            mode_for_terminal(IconMode::Emoji, supports_icons(&utf8)),
+            IconMode::Emoji
+        );
+
+        for term in ["linux", "dumb"] {
+            let vars = [("TERM", term), ("LANG", "en_US.UTF-8")];
+            assert!(!supports_icons(&vars));
+            assert_eq!(
+                mode_for_terminal(IconMode::Nerd, supports_icons(&vars)),
+                IconMode::None
+            );
+        }
+
+        // LC_ALL wins over LANG, and non-UTF-8 locales only matter where
+        // the terminal's encoding isn't assumed
+        let latin1 = [("TERM", "xterm"), ("LC_ALL", "C"), ("LANG", "en_US.UTF-8")];
+        assert_eq!(
+            supports_icons(&latin1),
+            cfg!(any(windows, target_os = "macos"))
+        );
+        assert_eq!(mode_for_terminal(IconMode::None, true), IconMode::None);
+    }
+}
@@ -1 +1,239 @@
-// This file is kept for future layout functionality but currently unused
+use super::template::Template;
+use crate::config::Config;
+use crate::info::Info;
+
+/// Prefix for literal text lines in a field list, e.g. `"text:Have a nice day"`
+const TEXT_PREFIX: &str = "text:";
+/// Prefix for title lines in a field list, e.g. `"title:Hardware"`
+const TITLE_PREFIX: &str = "title:";
+/// Field list entry for a separator line
+const SEPARATOR: &str = "---";
+/// Placeholders provided by the line being rendered rather than collected info
+const LINE_PLACEHOLDERS: &[&str] = &["label", "sep", "value", "field", "icon", "bar"];
+
+/// One line of the info column
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutItem {
+    Field(String),
+    Title(String),
+    Text(String),
+    Separator,
+    Blank,
+}
+
+/// Build the info column layout from `info.fields` followed by `info.sections`
+pub fn build_layout(config: &Config) -> Vec<LayoutItem> {
+    let mut items: Vec<LayoutItem> = config.info.fields.iter().map(|f| parse_entry(f)).collect();
+
+    for section in &config.info.sections {
+        // Keep sections apart, except at the very top or right after the header
+        let after_header = matches!(items.as_slice(), [LayoutItem::Field(f)] if f == "userhost");
+        if !items.is_empty() && !after_header && items.last() != Some(&LayoutItem::Blank) {
+            items.push(LayoutItem::Blank);
+        }
+
+        if let Some(title) = &section.title {
+            items.push(LayoutItem::Title(title.clone()));
+            if section.separator {
+                items.push(LayoutItem::Separator);
+            }
+        }
+
+        items.extend(section.fields.iter().map(|f| parse_entry(f)));
+    }
+
+    items
+}
+
+/// Replace fields that show one line per item with those items, e.g. `disk`
+/// with `disk:/` and `disk:/home` for each mount it was collected for
+pub fn expand(items: Vec<LayoutItem>, info: &Info) -> Vec<LayoutItem> {
+    items
+        .into_iter()
+        .flat_map(|item| match &item {
+            LayoutItem::Field(field) if !info.children(field).is_empty() => info
+                .children(field)
+                .iter()
+                .map(|child| LayoutItem::Field(child.clone()))
+                .collect(),
+            _ => vec![item],
+        })
+        .collect()
+}
+
+/// The built-in field an entry belongs to, e.g. `disk` for `disk:/home`
+pub fn base_field(field: &str) -> &str {
+    field.split(':').next().unwrap_or(field)
+}
+
+/// Names of the info fields that need to be collected for the layout,
+/// including fields only referenced from templates
+pub fn field_names(config: &Config) -> Vec<String> {
+    let templates = &config.display.templates;
+    let mut fields: Vec<String> = Vec::new();
+    let mut sources = vec![templates.line.clone()];
+
+    for item in build_layout(config) {
+        match item {
+            LayoutItem::Field(field) => {
+                if field == "userhost" {
+                    sources.push(templates.header.clone());
+                }
+                if let Some(source) = templates.fields.get(&field) {
+                    sources.push(source.clone());
+                }
+                fields.push(field);
+            }
+            LayoutItem::Text(text) => sources.push(text),
+            _ => {}
+        }
+    }
+
+    fields.extend(sources.iter().flat_map(|source| referenced_fields(source)));
+
+    let mut unique = Vec::new();
+    for field in fields {
+        if !unique.contains(&field) {
+            unique.push(field);
+        }
+    }
+    unique
+}
+
+/// Fields referenced by a template, e.g. `memory` for `{memory.used}`
+fn referenced_fields(source: &str) -> Vec<String> {
+    let Ok(template) = Template::parse(source) else {
+        return Vec::new();
+    };
+
+    template
+        .placeholders()
+        .into_iter()
+        .filter(|name| !LINE_PLACEHOLDERS.contains(name))
+        .map(|name| name.split('.').next().unwrap_or(name).to_string())
+        .collect()
+}
+
+fn parse_entry(entry: &str) -> LayoutItem {
+    if entry.trim().is_empty() {
+        LayoutItem::Blank
+    } else if entry == SEPARATOR {
+        LayoutItem::Separator
+    } else if let Some(text) = entry.strip_prefix(TEXT_PREFIX) {
+        LayoutItem::Text(text.to_string())
+    } else if let Some(title) = entry.strip_prefix(TITLE_PREFIX) {
+        LayoutItem::Title(title.to_string())
+    } else {
+        LayoutItem::Field(entry.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SectionConfig;
+    use crate::info::FieldValue;
+
+    fn strings(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|entry| entry.to_string()).collect()
+    }
+
+    fn field(name: &str) -> LayoutItem {
+        LayoutItem::Field(name.to_string())
+    }
+
+    #[test]
+    fn test_parse_entry() {
+        assert_eq!(parse_entry("os"), field("os"));
+        assert_eq!(parse_entry("---"), LayoutItem::Separator);
+        assert_eq!(parse_entry(""), LayoutItem::Blank);
+        assert_eq!(parse_entry("  "), LayoutItem::Blank);
+        assert_eq!(
+            parse_entry("title:Hardware"),
+            LayoutItem::Title("Hardware".to_string())
+        );
+        assert_eq!(
+            parse_entry("text:Up for {uptime}"),
+            LayoutItem::Text("Up for {uptime}".to_string())
+        );
+    }
+
+    #[test]
+    fn test_build_layout_sections() {
+        let mut config = Config::default();
+        config.info.fields = strings(&["userhost"]);
+        config.info.sections = vec![
+            SectionConfig {
+                title: Some("System".to_string()),
+                separator: true,
+                fields: strings(&["os", "kernel"]),
+            },
+            SectionConfig {
+                title: None,
+                separator: true,
+                fields: strings(&["cpu", "", "memory"]),
+            },
+        ];
+
+        assert_eq!(
+            build_layout(&config),
+            vec![
+                field("userhost"),
+                LayoutItem::Title("System".to_string()),
+                LayoutItem::Separator,
+                field("os"),
+                field("kernel"),
+                LayoutItem::Blank,
+                field("cpu"),
+                LayoutItem::Blank,
+                field("memory"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_disk() {
+        let items = vec![field("os"), field("disk"), LayoutItem::Separator];
+        let mut info = Info::default();
+
+        // Without collected mounts the field stays as it is
+        assert_eq!(expand(items.clone(), &info), items);
+
+        let mount = |value: &str| FieldValue::from(value.to_string());
+        info.insert(
+            "disk",
+            mount("20 GiB / 100 GiB")
+                .with_child("disk:/".to_string(), mount("20 GiB / 100 GiB"))
+                .with_child("disk:/home".to_string(), mount("1 TiB / 2 TiB")),
+        );
+        assert_eq!(
+            expand(items, &info),
+            vec![
+                field("os"),
+                field("disk:/"),
+                field("disk:/home"),
+                LayoutItem::Separator
+            ]
+        );
+    }
+
+    #[test]
+    fn test_field_names() {
+        let mut config = Config::default();
+        config.info.fields = strings(&["os", "text:Up for {uptime}", "---", "os"]);
+        config.display.templates.line = "{label}{sep}{value}".to_string();
+        assert_eq!(field_names(&config), strings(&["os", "uptime"]));
+    }
+
+    #[test]
+    fn test_invalid_text_is_literal() {
+        let mut config = Config::default();
+        config.info.fields = strings(&["text:Up for {uptime", "os"]);
+        let mut info = Info::default();
+        info.insert("os", FieldValue::from("Linux".to_string()));
+
+        let output = crate::display::render_info_with_colors(&info, &config).unwrap();
+        assert!(output.lines().next().unwrap().contains("Up for {uptime"));
+        assert!(output.contains("Linux"));
+    }
+}
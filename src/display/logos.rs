@@ -0,0 +1,290 @@
+use crate::config::loader::get_ascii_art_dir;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg"];
+
+/// Color used for logos that don't provide their own palette
+pub const DEFAULT_LOGO_COLOR: &str = "bright_blue";
+
+#[derive(Debug, Clone)]
+pub enum LogoSource {
+    Builtin(&'static str),
+    Text(PathBuf),
+    Image(PathBuf),
+}
+
+/// Optional sidecar metadata stored next to a logo as `<name>.toml`
+///
+/// ```toml
+/// colors = ["bright_blue", "white"]
+///
+/// [variants]
+/// small = "arch_small.txt"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LogoMeta {
+    #[serde(default)]
+    pub colors: Vec<String>,
+    #[serde(default)]
+    pub variants: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogoEntry {
+    pub name: String,
+    pub source: LogoSource,
+    pub meta: LogoMeta,
+}
+
+impl LogoEntry {
+    pub fn is_user(&self) -> bool {
+        !matches!(self.source, LogoSource::Builtin(_))
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self.source {
+            LogoSource::Image(_) => "image",
+            _ => "ascii",
+        }
+    }
+
+    /// Palette for this logo, falling back to the default logo color
+    pub fn colors(&self) -> Vec<String> {
+        if self.meta.colors.is_empty() {
+            vec![DEFAULT_LOGO_COLOR.to_string()]
+        } else {
+            self.meta.colors.clone()
+        }
+    }
+
+    /// Resolve the requested size variant, returning the entry itself if the
+    /// variant isn't declared in the metadata
+    pub fn variant(&self, size: Option<&str>) -> LogoSource {
+        let variant = size.and_then(|size| self.meta.variants.get(size));
+
+        match (variant, &self.source) {
+            (Some(file), LogoSource::Text(path) | LogoSource::Image(path)) => {
+                let path = path.with_file_name(file);
+                if is_image_path(&path) {
+                    LogoSource::Image(path)
+                } else {
+                    LogoSource::Text(path)
+                }
+            }
+            _ => self.source.clone(),
+        }
+    }
+}
+
+/// All logos available by name: the built-in set plus anything found in the
+/// user's ASCII art directory, which takes precedence over built-ins
+pub struct LogoRegistry {
+    entries: BTreeMap<String, LogoEntry>,
+}
+
+impl LogoRegistry {
+    pub fn load() -> Self {
+        let mut entries = BTreeMap::new();
+
+        for (name, art) in builtin_logos() {
+            entries.insert(
+                name.to_string(),
+                LogoEntry {
+                    name: name.to_string(),
+                    source: LogoSource::Builtin(art),
+                    meta: LogoMeta::default(),
+                },
+            );
+        }
+
+        match get_ascii_art_dir().and_then(|dir| load_user_logos(&dir)) {
+            Ok(user_logos) => {
+                for entry in user_logos {
+                    entries.insert(entry.name.clone(), entry);
+                }
+            }
+            Err(e) => tracing::debug!("Skipping user logos: {}", e),
+        }
+
+        Self { entries }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&LogoEntry> {
+        self.entries.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LogoEntry> {
+        self.entries.values()
+    }
+}
+
+/// Load the sidecar metadata for a logo file, if there is one. A sidecar that
+/// can't be read or parsed is reported and ignored, so the logo still shows.
+pub fn load_logo_meta(logo_path: &Path) -> LogoMeta {
+    read_logo_meta(logo_path).unwrap_or_else(|e| {
+        tracing::warn!("{:#}", e);
+        LogoMeta::default()
+    })
+}
+
+fn read_logo_meta(logo_path: &Path) -> Result<LogoMeta> {
+    let meta_path = logo_path.with_extension("toml");
+    if !meta_path.exists() {
+        return Ok(LogoMeta::default());
+    }
+
+    let content = fs::read_to_string(&meta_path)
+        .with_context(|| format!("Failed to read logo metadata: {}", meta_path.display()))?;
+
+    toml::from_str(&content)
+        .with_context(|| format!("Failed to parse logo metadata: {}", meta_path.display()))
+}
+
+fn load_user_logos(dir: &Path) -> Result<Vec<LogoEntry>> {
+    let mut logos = Vec::new();
+
+    if !dir.exists() {
+        return Ok(logos);
+    }
+
+    let read_dir = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read ASCII art directory: {}", dir.display()))?;
+
+    for path in read_dir.flatten().map(|entry| entry.path()) {
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+
+        let source = if is_image_path(&path) {
+            LogoSource::Image(path.clone())
+        } else if path.extension().is_some_and(|ext| ext == "txt") {
+            LogoSource::Text(path.clone())
+        } else {
+            continue;
+        };
+
+        logos.push(LogoEntry {
+            name: name.to_string(),
+            source,
+            meta: load_logo_meta(&path),
+        });
+    }
+
+    Ok(logos)
+}
+
+fn is_image_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// A built-in logo's art by name, bypassing user logos
+pub fn builtin_art(name: &str) -> Option<&'static str> {
+    builtin_logos()
+        .into_iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, art)| art)
+}
+
+fn builtin_logos() -> [(&'static str, &'static str); 7] {
+    [
+        ("default", include_str!("../assets/ascii/default.txt")),
+        ("macos", include_str!("../assets/ascii/macos.txt")),
+        ("linux", include_str!("../assets/ascii/linux.txt")),
+        ("ubuntu", include_str!("../assets/ascii/ubuntu.txt")),
+        ("arch", include_str!("../assets/ascii/arch.txt")),
+        ("debian", include_str!("../assets/ascii/debian.txt")),
+        ("fedora", include_str!("../assets/ascii/fedora.txt")),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_entry(path: &str, variants: &[(&str, &str)]) -> LogoEntry {
+        LogoEntry {
+            name: "test".to_string(),
+            source: LogoSource::Text(PathBuf::from(path)),
+            meta: LogoMeta {
+                colors: Vec::new(),
+                variants: variants
+                    .iter()
+                    .map(|(size, file)| (size.to_string(), file.to_string()))
+                    .collect(),
+            },
+        }
+    }
+
+    fn source_path(source: &LogoSource) -> Option<&Path> {
+        match source {
+            LogoSource::Text(path) | LogoSource::Image(path) => Some(path),
+            LogoSource::Builtin(_) => None,
+        }
+    }
+
+    #[test]
+    fn test_variant_selection() {
+        let entry = text_entry(
+            "/logos/arch.txt",
+            &[("small", "arch_small.txt"), ("large", "arch.png")],
+        );
+
+        let small = entry.variant(Some("small"));
+        assert!(matches!(small, LogoSource::Text(_)));
+        assert_eq!(
+            source_path(&small),
+            Some(Path::new("/logos/arch_small.txt"))
+        );
+
+        let large = entry.variant(Some("large"));
+        assert!(matches!(large, LogoSource::Image(_)));
+        assert_eq!(source_path(&large), Some(Path::new("/logos/arch.png")));
+
+        // Undeclared sizes and no size keep the logo itself
+        for size in [Some("tiny"), None] {
+            assert_eq!(
+                source_path(&entry.variant(size)),
+                Some(Path::new("/logos/arch.txt"))
+            );
+        }
+
+        let builtin = LogoEntry {
+            source: LogoSource::Builtin("art"),
+            ..text_entry("", &[("small", "small.txt")])
+        };
+        assert!(matches!(
+            builtin.variant(Some("small")),
+            LogoSource::Builtin("art")
+        ));
+    }
+
+    #[test]
+    fn test_bad_sidecar_falls_back_to_defaults() {
+        let dir = std::env::temp_dir().join(format!("rice-logos-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let logo = dir.join("broken.txt");
+        fs::write(&logo, "art").unwrap();
+        fs::write(dir.join("broken.toml"), "colors = [\"red\"\n").unwrap();
+
+        let meta = load_logo_meta(&logo);
+        assert!(meta.colors.is_empty() && meta.variants.is_empty());
+
+        fs::write(dir.join("broken.toml"), "colors = [\"red\"]\n").unwrap();
+        assert_eq!(load_logo_meta(&logo).colors, vec!["red"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_missing_dir_has_no_user_logos() {
+        let dir = std::env::temp_dir().join(format!("rice-no-logos-{}", std::process::id()));
+        assert!(load_user_logos(&dir).unwrap().is_empty());
+        assert!(!dir.exists());
+    }
+}
@@ -1,22 +1,43 @@
+pub mod ansi;
 pub mod ascii_art;
+pub mod bars;
+pub mod export;
+pub mod icons;
 pub mod layout;
+pub mod logos;
+pub mod output;
+pub mod report;
+pub mod template;
 pub mod themes;
+pub mod watch;
 
-use crate::config::Config;
-use anyhow::Result;
+use crate::config::{BarMode, Config, IconMode, IconPlacement, PlainColors};
+use crate::info::Info;
+use crate::utils::terminal::{self, display_width};
+use anyhow::{Context, Result};
 use colored::*;
+use layout::LayoutItem;
+use std::cell::OnceCell;
 use std::collections::HashMap;
+use std::path::Path;
+use template::{Template, TemplateContext};
+use unicode_width::UnicodeWidthChar;
 
 pub struct Display {
     config: Config,
+    /// Resolved on first render and reused by redraws in `--watch`
+    logo: OnceCell<ascii_art::Logo>,
 }
 
 impl Display {
     pub fn new(config: Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            logo: OnceCell::new(),
+        }
     }
 
-    pub fn render(&self, info: &HashMap<String, String>) -> Result<String> {
+    pub fn render(&self, info: &Info) -> Result<String> {
         if self.config.display.show_logo {
             self.render_with_ascii(info)
         } else {
@@ -24,13 +45,36 @@ impl Display {
         }
     }
 
-    fn render_with_ascii(&self, info: &HashMap<String, String>) -> Result<String> {
+    /// Render as a Markdown report for pasting into issues and wikis
+    pub fn render_markdown(&self, info: &Info) -> Result<String> {
+        report::markdown(info, &self.config)
+    }
+
+    /// Render as a standalone HTML page
+    pub fn render_html(&self, info: &Info) -> Result<String> {
+        report::html(self, info)
+    }
+
+    /// Render to an SVG or PNG screenshot
+    pub fn export(&self, info: &Info, path: &Path) -> Result<()> {
+        export::export(self, info, path)
+    }
+
+    fn logo(&self) -> Result<&ascii_art::Logo> {
+        if let Some(logo) = self.logo.get() {
+            return Ok(logo);
+        }
         let logo = ascii_art::get_ascii_art(&self.config)?;
+        Ok(self.logo.get_or_init(|| logo))
+    }
+
+    fn render_with_ascii(&self, info: &Info) -> Result<String> {
+        let logo = self.logo()?;
 
         // Check if this is a terminal inline image
-        let is_terminal_image = logo.starts_with("\x1b]1337;")
-            || logo.starts_with("\x1bPtmux;")
-            || logo.starts_with("\x1b_Ga=");
+        let is_terminal_image = logo.art.starts_with("\x1b]1337;")
+            || logo.art.starts_with("\x1bPtmux;")
+            || logo.art.starts_with("\x1b_Ga=");
 
         if is_terminal_image {
             // Neofetch-style image rendering with side-by-side text
@@ -41,7 +85,7 @@ impl Display {
             let info_lines: Vec<&str> = info_output.lines().collect();
 
             // First, output the image (it will render immediately)
-            output.push_str(logo.trim_end());
+            output.push_str(logo.art.trim_end());
 
             // The image is rendered in character cells (30 wide x 15 tall)
             // We need to move the cursor up to the top of the image area
@@ -71,8 +115,13 @@ impl Display {
 
             Ok(output)
         } else {
-            // Regular ASCII art handling
-            let logo_lines: Vec<&str> = logo.lines().collect();
+            // Regular ASCII art handling, with `${cN}` markers selecting palette colors
+            let mut color_index = 0;
+            let logo_lines: Vec<(String, usize)> = logo
+                .art
+                .lines()
+                .map(|line| colorize_logo_line(line, &logo.colors, &mut color_index))
+                .collect();
 
             // Get info lines
             let info_output = render_info_with_colors(info, &self.config)?;
@@ -84,19 +133,17 @@ impl Display {
             // Find the max width of logo lines for proper spacing
             let logo_width = logo_lines
                 .iter()
-                .map(|line| line.chars().count())
+                .map(|(_, width)| *width)
                 .max()
                 .unwrap_or(0);
 
+            let empty_line = (String::new(), 0);
             for i in 0..max_lines {
-                let logo_line = logo_lines.get(i).unwrap_or(&"").to_string();
+                let (colored_logo_line, logo_char_count) = logo_lines.get(i).unwrap_or(&empty_line);
+                let logo_char_count = *logo_char_count;
                 let info_line = info_lines.get(i).unwrap_or(&"");
 
-                // Color the logo line
-                let colored_logo_line = logo_line.bright_blue();
-
                 // Calculate padding needed
-                let logo_char_count = logo_line.chars().count();
                 let padding = if logo_char_count < logo_width {
                     " ".repeat(logo_width - logo_char_count + 2)
                 } else {
@@ -105,7 +152,7 @@ impl Display {
 
                 // Combine logo and info
                 if info_line.is_empty() {
-                    output.push_str(&colored_logo_line.to_string());
+                    output.push_str(colored_logo_line);
                 } else {
                     output.push_str(&format!("{}{}{}", colored_logo_line, padding, info_line));
                 }
@@ -117,51 +164,333 @@ impl Display {
     }
 }
 
-fn render_info_with_colors(info: &HashMap<String, String>, config: &Config) -> Result<String> {
+/// Color a logo line using its palette, returning the colored line and its visible width.
+/// `${c1}`..`${c9}` switch to the matching palette color for the rest of the logo.
+fn colorize_logo_line(line: &str, colors: &[String], color_index: &mut usize) -> (String, usize) {
+    let mut output = String::new();
+    let mut width = 0;
+    let mut segment = String::new();
+    let mut rest = line;
+
+    while !rest.is_empty() {
+        let marker = rest
+            .strip_prefix("${c")
+            .and_then(|tail| tail.split_once('}'))
+            .and_then(|(digits, tail)| Some((digits.parse::<usize>().ok()?, tail)))
+            .filter(|(n, _)| *n > 0);
+
+        if let Some((n, tail)) = marker {
+            push_logo_segment(&mut output, &mut segment, colors, *color_index);
+            *color_index = (n - 1).min(colors.len().saturating_sub(1));
+            rest = tail;
+        } else {
+            let c = rest.chars().next().unwrap_or_default();
+            segment.push(c);
+            width += UnicodeWidthChar::width(c).unwrap_or(0);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    push_logo_segment(&mut output, &mut segment, colors, *color_index);
+
+    (output, width)
+}
+
+fn push_logo_segment(output: &mut String, segment: &mut String, colors: &[String], index: usize) {
+    if segment.is_empty() {
+        return;
+    }
+    let color = colors
+        .get(index)
+        .map(|s| s.as_str())
+        .unwrap_or(logos::DEFAULT_LOGO_COLOR);
+    output.push_str(&apply_color_by_name(segment, color));
+    segment.clear();
+}
+
+/// Names of the terminal palette colors shown by the `colors` field
+const PALETTE_NAMES: &[&str] = &[
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+fn render_info_with_colors(info: &Info, config: &Config) -> Result<String> {
+    // Without colors the color blocks are meaningless, so describe or drop them
+    let plain_info;
+    let info = if info.contains("colors") && !terminal::colors_enabled() {
+        let mut stripped = info.clone();
+        match config.display.plain_colors {
+            PlainColors::Hide => stripped.remove("colors"),
+            PlainColors::Text => stripped.set("colors", PALETTE_NAMES.join(" ")),
+        }
+        plain_info = stripped;
+        &plain_info
+    } else {
+        info
+    };
+
+    let templates = &config.display.templates;
+    let line_template = parse_template(&templates.line)?;
+    let mut field_templates = HashMap::new();
+    for (field, source) in &templates.fields {
+        field_templates.insert(field.as_str(), parse_template(source)?);
+    }
+
+    let icon_mode = icons::effective_mode(&config.display.icons);
     let mut output = String::new();
 
     // Add userhost header if available, like neofetch
-    if let Some(userhost) = info.get("userhost") {
-        output.push_str(&userhost.bright_green().bold().to_string());
-        output.push('\n');
-        // Add separator line
-        let separator = "-".repeat(userhost.len());
-        output.push_str(&separator.dimmed().to_string());
+    if info.contains("userhost") {
+        let header_template = parse_template(&templates.header)?;
+        let context = LineContext::new("userhost", info, config, icon_mode, &header_template);
+        let header = header_template.render(&context);
+
+        if !header.is_empty() {
+            output.push_str(&header);
+            output.push('\n');
+            // Add separator line
+            if !templates.underline.is_empty() {
+                let separator = templates.underline.repeat(display_width(&header));
+                output.push_str(&separator.dimmed().to_string());
+                output.push('\n');
+            }
+        }
+    }
+
+    // Separators span the widest line, so they're filled in once all lines are known
+    let mut lines: Vec<Option<String>> = Vec::new();
+
+    for item in layout::expand(layout::build_layout(config), info) {
+        match item {
+            // Skip userhost since it's shown at top
+            LayoutItem::Field(field) if field == "userhost" => {}
+            LayoutItem::Field(field) => {
+                let Some(value) = info.get(&field) else {
+                    continue;
+                };
+
+                // Special handling for colors field - can show without label
+                if field == "colors" && !config.display.show_colors_label {
+                    lines.push(Some(value.clone()));
+                } else {
+                    let template = field_templates
+                        .get(field.as_str())
+                        .or_else(|| field_templates.get(layout::base_field(&field)))
+                        .unwrap_or(&line_template);
+                    let context = LineContext::new(&field, info, config, icon_mode, template);
+                    lines.push(Some(template.render(&context)));
+                }
+            }
+            LayoutItem::Title(title) => {
+                let color = config
+                    .display
+                    .field_colors
+                    .get("title")
+                    .map(|s| s.as_str())
+                    .unwrap_or("bright_white");
+                lines.push(Some(apply_color_by_name(&title, color).bold().to_string()));
+            }
+            LayoutItem::Text(text) => {
+                if let Some(line) = render_text(&text, info, config) {
+                    lines.push(Some(line));
+                }
+            }
+            LayoutItem::Blank => lines.push(Some(String::new())),
+            LayoutItem::Separator => lines.push(None),
+        }
+    }
+
+    let width = lines
+        .iter()
+        .flatten()
+        .flat_map(|line| line.lines())
+        .map(display_width)
+        .max()
+        .unwrap_or(0);
+    let underline = if templates.underline.is_empty() {
+        "-"
+    } else {
+        templates.underline.as_str()
+    };
+
+    for line in lines {
+        let line = line.unwrap_or_else(|| underline.repeat(width).dimmed().to_string());
+        output.push_str(&line);
         output.push('\n');
     }
 
-    // Use the field order from config instead of hardcoded array
-    for field in &config.info.fields {
-        // Skip userhost since it's shown at top
-        if field == "userhost" {
-            continue;
+    Ok(output)
+}
+
+/// Render a literal line, which may reference collected values, e.g.
+/// "Up for {uptime}". The line is left out when none of its values were
+/// collected, and a line that isn't a valid template is shown as written.
+fn render_text(text: &str, info: &Info, config: &Config) -> Option<String> {
+    match parse_template(text) {
+        Ok(template) => {
+            let context = LineContext::new("", info, config, IconMode::None, &template);
+            template.render_if_any(&context)
+        }
+        Err(e) => {
+            tracing::warn!("{:#}", e);
+            Some(text.to_string())
         }
+    }
+}
 
-        if let Some(value) = info.get(field) {
-            // Special handling for colors field - can show without label
-            if field == "colors" && !config.display.show_colors_label {
-                output.push_str(value);
-                output.push('\n');
-            } else {
-                let label = get_field_label(field);
-                let line = format!(
-                    "{}{} {}",
-                    label.cyan().bold(),
-                    ":".dimmed(),
-                    colorize_value(
-                        field,
-                        value,
-                        config.display.color_values,
-                        &config.display.field_colors
-                    )
+fn parse_template(source: &str) -> Result<Template> {
+    Template::parse(source).with_context(|| format!("Invalid template: \"{}\"", source))
+}
+
+/// Template values for a single info line
+struct LineContext<'a> {
+    field: &'a str,
+    info: &'a Info,
+    config: &'a Config,
+    icon: Option<String>,
+    // Whether the icon is added to the label, for templates without `{icon}`
+    icon_in_label: bool,
+    bar_percent: Option<f64>,
+    // Whether the bar is part of the value, for templates without `{bar}`
+    bar_in_value: bool,
+}
+
+impl<'a> LineContext<'a> {
+    fn new(
+        field: &'a str,
+        info: &'a Info,
+        config: &'a Config,
+        icon_mode: IconMode,
+        template: &Template,
+    ) -> Self {
+        let base = layout::base_field(field);
+        let icon = icons::get_field_icon(field, icon_mode, &config.display.icons)
+            .or_else(|| icons::get_field_icon(base, icon_mode, &config.display.icons));
+
+        let bars = &config.display.bars;
+        let bar_percent = info
+            .get(&format!("{}.percent", field))
+            .and_then(|percent| percent.parse::<f64>().ok())
+            .filter(|_| {
+                bars.fields.is_empty() || bars.fields.iter().any(|f| f == field || f == base)
+            });
+
+        Self {
+            field,
+            info,
+            config,
+            icon_in_label: icon.is_some() && !template.references("icon"),
+            icon,
+            bar_percent,
+            bar_in_value: bar_percent.is_some()
+                && bars.mode != BarMode::Value
+                && !template.references("bar"),
+        }
+    }
+
+    fn bar(&self) -> Option<String> {
+        self.bar_percent
+            .map(|percent| bars::render_bar(percent, &self.config.display.bars))
+    }
+
+    fn paint_bar(&self) -> Option<String> {
+        self.bar_percent
+            .map(|percent| bars::paint_bar(self.field, percent, &self.config.display.bars))
+    }
+
+    fn replaces_label(&self) -> bool {
+        self.icon_in_label && self.config.display.icons.placement == IconPlacement::Replace
+    }
+}
+
+impl TemplateContext for LineContext<'_> {
+    fn resolve(&self, name: &str) -> Option<String> {
+        match name {
+            "icon" => self.icon.clone(),
+            "label" => {
+                let label = field_label(self.field, self.config);
+                match &self.icon {
+                    Some(icon) if self.replaces_label() => Some(icon.clone()),
+                    Some(icon) if self.icon_in_label => Some(format!("{} {}", icon, label)),
+                    _ => Some(label),
+                }
+            }
+            // An icon on its own reads better without the label separator
+            "sep" if self.replaces_label() => None,
+            "sep" => Some(self.config.display.templates.separator.clone()),
+            "value" if self.bar_in_value => {
+                let value = self.info.get(self.field)?;
+                let bar = self.bar()?;
+                match self.config.display.bars.mode {
+                    BarMode::Bar => Some(bar),
+                    _ => Some(format!("{} {}", value, bar)),
+                }
+            }
+            "value" => self.info.get(self.field).cloned(),
+            "bar" => self.bar(),
+            "field" => Some(self.field.to_string()),
+            _ => self.info.get(name).cloned(),
+        }
+    }
+
+    fn paint(&self, name: &str, text: &str) -> String {
+        match name {
+            "label" | "icon" => text.cyan().bold().to_string(),
+            "sep" => text.dimmed().to_string(),
+            "field" => text.to_string(),
+            "userhost" => text.bright_green().bold().to_string(),
+            "bar" => text.replacen(
+                &self.bar().unwrap_or_default(),
+                &self.paint_bar().unwrap_or_default(),
+                1,
+            ),
+            "value" if self.bar_in_value => {
+                // Color the value and bar separately, keeping any padding
+                let (bar, painted_bar) = (
+                    self.bar().unwrap_or_default(),
+                    self.paint_bar().unwrap_or_default(),
                 );
-                output.push_str(&line);
-                output.push('\n');
+                match text.rfind(&bar) {
+                    Some(pos) => format!(
+                        "{}{}{}",
+                        colorize_value(
+                            self.field,
+                            &text[..pos],
+                            self.config.display.color_values,
+                            &self.config.display.field_colors,
+                        ),
+                        painted_bar,
+                        &text[pos + bar.len()..]
+                    ),
+                    None => text.to_string(),
+                }
+            }
+            _ => {
+                // Named values take the color of the field they belong to
+                let field = if name == "value" {
+                    self.field
+                } else {
+                    name.split('.').next().unwrap_or(name)
+                };
+                colorize_value(
+                    field,
+                    text,
+                    self.config.display.color_values,
+                    &self.config.display.field_colors,
+                )
             }
         }
     }
+}
 
-    Ok(output)
+/// Label for a field, preferring `display.labels` overrides. Entries like
+/// `disk:/home` get their base field's label with the argument, "Disk (/home)".
+fn field_label(field: &str, config: &Config) -> String {
+    if let Some(label) = config.display.labels.get(field) {
+        return label.clone();
+    }
+    match field.split_once(':') {
+        Some((base, argument)) => format!("{} ({})", field_label(base, config), argument),
+        None => get_field_label(field),
+    }
 }
 
 fn get_field_label(field: &str) -> String {
@@ -175,11 +504,31 @@ fn get_field_label(field: &str) -> String {
         "resolution" => "Resolution".to_string(),
         "de" => "DE".to_string(),
         "wm" => "WM".to_string(),
+        "session" => "Session".to_string(),
+        "virtualization" => "Virtualization".to_string(),
+        "init" => "Init".to_string(),
+        "failed_units" => "Failed Units".to_string(),
+        "locale" => "Locale".to_string(),
+        "timezone" => "Timezone".to_string(),
+        "users" => "Users".to_string(),
+        "boot_time" => "Boot Time".to_string(),
+        "datetime" => "Date".to_string(),
+        "theme" => "Theme".to_string(),
+        "icons" => "Icons".to_string(),
+        "cursor" => "Cursor".to_string(),
+        "font" => "Font".to_string(),
         "terminal" => "Terminal".to_string(),
         "terminal_font" => "Terminal Font".to_string(),
         "cpu" => "CPU".to_string(),
+        "cpu_usage" => "CPU Usage".to_string(),
+        "cpu_cache" => "CPU Cache".to_string(),
+        "load" => "Load".to_string(),
         "memory" => "Memory".to_string(),
+        "memory_details" => "Memory Details".to_string(),
+        "swap" => "Swap".to_string(),
         "disk" => "Disk".to_string(),
+        "battery" => "Battery".to_string(),
+        "temperature" => "Temperature".to_string(),
         "colors" => "Colors".to_string(),
         // For custom fields, capitalize first letter and replace underscores with spaces
         _ => {
@@ -210,7 +559,11 @@ fn colorize_value(
 
     // Get color from config, fallback to white
     let default_color = "white".to_string();
-    let color_name = field_colors.get(field).unwrap_or(&default_color).as_str();
+    let color_name = field_colors
+        .get(field)
+        .or_else(|| field_colors.get(layout::base_field(field)))
+        .unwrap_or(&default_color)
+        .as_str();
 
     // Apply color based on config
     apply_color_by_name(value, color_name)
@@ -0,0 +1,296 @@
+use crate::info::metrics::Metric;
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use serde_json::Value;
+
+/// Prefix for variables printed by the `env` format
+const ENV_PREFIX: &str = "RICE";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Formatted, colored output
+    Text,
+    Json,
+    Yaml,
+    Toml,
+    /// Shell-safe KEY='value' lines, usable with eval
+    Env,
+    /// Prometheus text exposition format, for node_exporter's textfile collector
+    Prometheus,
+    /// Label/value table for issues and wikis
+    Markdown,
+    /// Standalone page keeping the display colors
+    Html,
+}
+
+/// Serialize collected data in a machine-readable format. `root` names the
+/// data when the format needs a top-level table (TOML) and the data isn't one.
+pub fn serialize(value: &Value, format: OutputFormat, root: &str) -> Result<String> {
+    match format {
+        OutputFormat::Text | OutputFormat::Json => {
+            serde_json::to_string_pretty(value).context("Failed to serialize info to JSON")
+        }
+        OutputFormat::Yaml => {
+            let mut output = String::new();
+            write_yaml(value, 0, &mut output);
+            Ok(output.trim_end().to_string())
+        }
+        OutputFormat::Toml => {
+            let table = match value {
+                Value::Object(_) => value.clone(),
+                _ => serde_json::json!({ root: value }),
+            };
+            toml::to_string(&table)
+                .map(|s| s.trim_end().to_string())
+                .context("Failed to serialize info to TOML")
+        }
+        OutputFormat::Env => {
+            let mut lines = Vec::new();
+            let prefix = match value {
+                Value::Object(_) => ENV_PREFIX.to_string(),
+                _ => format!("{}_{}", ENV_PREFIX, env_name(root)),
+            };
+            write_env(value, &prefix, &mut lines);
+            Ok(lines.join("\n"))
+        }
+        OutputFormat::Prometheus => bail!("Prometheus output is rendered from metrics"),
+        OutputFormat::Markdown | OutputFormat::Html => {
+            bail!("Markdown and HTML output are only available for the info display")
+        }
+    }
+}
+
+/// Render metrics in the Prometheus text exposition format
+pub fn prometheus(metrics: &[Metric]) -> String {
+    let mut output = String::new();
+    let mut names: Vec<&str> = Vec::new();
+
+    for metric in metrics {
+        if !names.contains(&metric.name) {
+            names.push(metric.name);
+        }
+    }
+
+    for name in names {
+        let mut samples = metrics.iter().filter(|m| m.name == name).peekable();
+        if let Some(first) = samples.peek() {
+            output.push_str(&format!("# HELP {} {}\n", name, first.help));
+            output.push_str(&format!("# TYPE {} {}\n", name, first.kind.as_str()));
+        }
+
+        for metric in samples {
+            output.push_str(name);
+            if !metric.labels.is_empty() {
+                let labels: Vec<String> = metric
+                    .labels
+                    .iter()
+                    .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+                    .collect();
+                output.push_str(&format!("{{{}}}", labels.join(",")));
+            }
+            output.push_str(&format!(" {}\n", metric.value));
+        }
+    }
+
+    output
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn write_yaml(value: &Value, indent: usize, output: &mut String) {
+    let pad = " ".repeat(indent);
+
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                if is_yaml_block(child) {
+                    output.push_str(&format!("{}{}:\n", pad, yaml_key(key)));
+                    write_yaml(child, indent + 2, output);
+                } else {
+                    output.push_str(&format!(
+                        "{}{}: {}\n",
+                        pad,
+                        yaml_key(key),
+                        yaml_scalar(child)
+                    ));
+                }
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for item in items {
+                if is_yaml_block(item) {
+                    // Render the nested block one level deeper, then turn its
+                    // leading indentation into the list marker
+                    let mut block = String::new();
+                    write_yaml(item, indent + 2, &mut block);
+                    output.push_str(&format!("{}- {}", pad, &block[indent + 2..]));
+                } else {
+                    output.push_str(&format!("{}- {}\n", pad, yaml_scalar(item)));
+                }
+            }
+        }
+        _ => output.push_str(&format!("{}{}\n", pad, yaml_scalar(value))),
+    }
+}
+
+fn is_yaml_block(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => !map.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        _ => false,
+    }
+}
+
+fn yaml_key(key: &str) -> String {
+    let plain = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+
+    if plain {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Object(_) => "{}".to_string(),
+        Value::Array(_) => "[]".to_string(),
+        // JSON strings are valid double-quoted YAML scalars
+        other => other.to_string(),
+    }
+}
+
+fn write_env(value: &Value, name: &str, lines: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                write_env(child, &format!("{}_{}", name, env_name(key)), lines);
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                write_env(item, &format!("{}_{}", name, i), lines);
+            }
+        }
+        Value::String(s) => lines.push(format!("{}={}", name, shell_quote(s))),
+        Value::Null => lines.push(format!("{}=''", name)),
+        other => lines.push(format!("{}={}", name, shell_quote(&other.to_string()))),
+    }
+}
+
+/// Turn a key into a valid shell variable name component
+fn env_name(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Single-quote a value for POSIX shells
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::info::metrics::MetricKind;
+    use serde_json::json;
+
+    #[test]
+    fn test_env_output() {
+        let value = json!({ "os": "Linux", "memory.used": "1 GB", "shell": "it's" });
+        assert_eq!(
+            serialize(&value, OutputFormat::Env, "info").unwrap(),
+            "RICE_MEMORY_USED='1 GB'\nRICE_OS='Linux'\nRICE_SHELL='it'\\''s'"
+        );
+
+        let value = json!([{ "core": 0, "usage": 1.5 }]);
+        assert_eq!(
+            serialize(&value, OutputFormat::Env, "cpus").unwrap(),
+            "RICE_CPUS_0_CORE='0'\nRICE_CPUS_0_USAGE='1.5'"
+        );
+    }
+
+    #[test]
+    fn test_yaml_output() {
+        let value = json!({
+            "name": "rice",
+            "disks": [{ "mount": "/", "size": 10 }, { "mount": "/home", "size": 20 }],
+            "tags": ["a", "b"],
+            "empty": []
+        });
+        assert_eq!(
+            serialize(&value, OutputFormat::Yaml, "info").unwrap(),
+            "disks:\n  - mount: \"/\"\n    size: 10\n  - mount: \"/home\"\n    size: 20\n\
+             empty: []\nname: \"rice\"\ntags:\n  - \"a\"\n  - \"b\""
+        );
+    }
+
+    #[test]
+    fn test_prometheus_output() {
+        let metrics = vec![
+            Metric {
+                name: "rice_info",
+                help: "Facts",
+                kind: MetricKind::Gauge,
+                labels: vec![("os", "Linux \"x\"".to_string())],
+                value: 1.0,
+            },
+            Metric {
+                name: "rice_disk_total_bytes",
+                help: "Disk",
+                kind: MetricKind::Gauge,
+                labels: vec![("mount", "/".to_string())],
+                value: 1024.0,
+            },
+            Metric {
+                name: "rice_disk_total_bytes",
+                help: "Disk",
+                kind: MetricKind::Gauge,
+                labels: vec![("mount", "/home".to_string())],
+                value: 2048.5,
+            },
+            Metric {
+                name: "rice_network_received_bytes_total",
+                help: "Received",
+                kind: MetricKind::Counter,
+                labels: vec![("interface", "eth0".to_string())],
+                value: 4096.0,
+            },
+        ];
+
+        assert_eq!(
+            prometheus(&metrics),
+            "# HELP rice_info Facts\n# TYPE rice_info gauge\nrice_info{os=\"Linux \\\"x\\\"\"} 1\n\
+             # HELP rice_disk_total_bytes Disk\n# TYPE rice_disk_total_bytes gauge\n\
+             rice_disk_total_bytes{mount=\"/\"} 1024\nrice_disk_total_bytes{mount=\"/home\"} 2048.5\n\
+             # HELP rice_network_received_bytes_total Received\n\
+             # TYPE rice_network_received_bytes_total counter\n\
+             rice_network_received_bytes_total{interface=\"eth0\"} 4096\n"
+        );
+    }
+
+    #[test]
+    fn test_toml_wraps_arrays() {
+        let value = json!([{ "core": 0 }]);
+        assert_eq!(
+            serialize(&value, OutputFormat::Toml, "cpus").unwrap(),
+            "[[cpus]]\ncore = 0"
+        );
+    }
+}
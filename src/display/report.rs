@@ -0,0 +1,211 @@
+use super::ansi::{self, escape_html};
+use super::layout::{self, LayoutItem};
+use super::{ascii_art, colorize_logo_line, field_label, render_text, Display};
+use crate::config::Config;
+use crate::info::Info;
+use crate::utils::terminal::strip_ansi;
+use anyhow::Result;
+use std::path::Path;
+
+/// Render collected info as a Markdown label/value table, with the logo in a
+/// fenced block when logos are enabled
+pub fn markdown(info: &Info, config: &Config) -> Result<String> {
+    let mut output = String::new();
+
+    if let Some(userhost) = info.get("userhost") {
+        output.push_str(&format!("## {}\n\n", strip_ansi(userhost)));
+    }
+
+    if config.display.show_logo {
+        let logo = ascii_art::get_ascii_art(config)?;
+        if !logo.art.trim().is_empty() {
+            let mut color_index = 0;
+            output.push_str("```text\n");
+            for line in logo.art.lines() {
+                let (line, _) = colorize_logo_line(line, &logo.colors, &mut color_index);
+                output.push_str(strip_ansi(&line).trim_end());
+                output.push('\n');
+            }
+            output.push_str("```\n\n");
+        }
+    }
+
+    // Tables are started lazily so titles can sit between them as headings
+    let mut in_table = false;
+    for item in layout::expand(layout::build_layout(config), info) {
+        let row = match item {
+            LayoutItem::Field(field) if field == "userhost" || field == "colors" => continue,
+            LayoutItem::Field(field) => match info.get(&field) {
+                Some(value) => (field_label(&field, config), strip_ansi(value)),
+                None => continue,
+            },
+            LayoutItem::Text(text) => match render_text(&text, info, config) {
+                Some(line) => (String::new(), strip_ansi(&line)),
+                None => continue,
+            },
+            LayoutItem::Title(title) => {
+                if in_table {
+                    output.push('\n');
+                    in_table = false;
+                }
+                output.push_str(&format!("### {}\n\n", title));
+                continue;
+            }
+            LayoutItem::Separator | LayoutItem::Blank => continue,
+        };
+
+        if !in_table {
+            output.push_str("| Field | Value |\n| --- | --- |\n");
+            in_table = true;
+        }
+        output.push_str(&format!(
+            "| {} | {} |\n",
+            markdown_cell(&row.0),
+            markdown_cell(&row.1)
+        ));
+    }
+
+    Ok(output.trim_end().to_string())
+}
+
+/// Render collected info as a standalone HTML page that keeps the terminal colors
+pub fn html(display: &Display, info: &Info) -> Result<String> {
+    let config = &display.config;
+    let title = info
+        .get("userhost")
+        .map(|userhost| strip_ansi(userhost))
+        .unwrap_or_else(|| "rice".to_string());
+
+    let body = match logo_image(config) {
+        Some(image) => format!(
+            "<img src=\"{}\" alt=\"logo\">\n<pre>{}</pre>",
+            image,
+            ansi::to_html(&super::render_info_with_colors(info, config)?)
+        ),
+        None => format!("<pre>{}</pre>", ansi::to_html(&display.render(info)?)),
+    };
+
+    Ok(format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{}</title>
+<style>
+body {{ background: #1d1f21; color: #c5c8c6; margin: 2em; }}
+.rice {{ display: flex; gap: 2em; align-items: flex-start; }}
+.rice img {{ width: 240px; height: auto; }}
+pre {{ font-family: ui-monospace, "DejaVu Sans Mono", Menlo, Consolas, monospace; line-height: 1.2; margin: 0; }}
+</style>
+</head>
+<body>
+<div class="rice">
+{}
+</div>
+</body>
+</html>"#,
+        escape_html(&title),
+        body
+    ))
+}
+
+fn markdown_cell(text: &str) -> String {
+    text.trim_end().replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// The logo as a data URI when it is an image, so the page needs no other files
+fn logo_image(config: &Config) -> Option<String> {
+    if !config.display.show_logo {
+        return None;
+    }
+
+    image_data_uri(&ascii_art::logo_image_path(config)?)
+}
+
+/// Read a PNG or JPEG image into a `data:` URI
+#[cfg(feature = "images")]
+pub fn image_data_uri(path: &Path) -> Option<String> {
+    use base64::Engine;
+
+    let mime = match path.extension()?.to_str()?.to_lowercase().as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        _ => return None,
+    };
+
+    match std::fs::read(path) {
+        Ok(data) => Some(format!(
+            "data:{};base64,{}",
+            mime,
+            base64::engine::general_purpose::STANDARD.encode(data)
+        )),
+        Err(e) => {
+            tracing::warn!("Failed to read logo image {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "images"))]
+pub fn image_data_uri(_path: &Path) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::info::FieldValue;
+
+    fn test_info(fields: &[(&str, &str)]) -> Info {
+        let mut info = Info::default();
+        for (field, value) in fields {
+            info.insert(field, FieldValue::from(value.to_string()));
+        }
+        info
+    }
+
+    fn test_config(fields: &[&str]) -> Config {
+        let mut config = Config::default();
+        config.display.show_logo = false;
+        config.info.fields = fields.iter().map(|field| field.to_string()).collect();
+        config
+    }
+
+    #[test]
+    fn test_markdown_report() {
+        let info = test_info(&[
+            ("userhost", "alice@box"),
+            ("os", "Arch | Linux"),
+            ("colors", "███"),
+            ("uptime", "3 hours"),
+        ]);
+        let config = test_config(&[
+            "userhost",
+            "os",
+            "colors",
+            "title:Hardware",
+            "text:Up for {uptime}",
+            "text:Booted {boot_time}",
+        ]);
+
+        assert_eq!(
+            markdown(&info, &config).unwrap(),
+            "## alice@box\n\n\
+             | Field | Value |\n| --- | --- |\n| OS | Arch \\| Linux |\n\n\
+             ### Hardware\n\n\
+             | Field | Value |\n| --- | --- |\n|  | Up for 3 hours |"
+        );
+    }
+
+    #[test]
+    fn test_html_page() {
+        let info = test_info(&[("userhost", "<alice>@box"), ("os", "Tom & Jerry OS")]);
+        let display = Display::new(test_config(&["os"]));
+
+        let page = html(&display, &info).unwrap();
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains("<title>&lt;alice&gt;@box</title>"));
+        assert!(page.contains("Tom &amp; Jerry OS"));
+        assert!(!page.contains("\x1b["));
+    }
+}
@@ -0,0 +1,345 @@
+//! A small template language for info lines.
+//!
+//! Placeholders are written as `{name}` or `{name:spec}`, where `spec` follows
+//! Rust's format syntax: `[[fill]align][width][.precision]` with `<`, `>` or `^`
+//! for alignment. Precision rounds numeric values and truncates anything else.
+//! Text inside `[...]` is only rendered when every placeholder within it has a
+//! value. Use `{{`, `}}`, `[[` and `]]` for literal braces and brackets.
+
+use crate::utils::terminal::display_width;
+use anyhow::{bail, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Placeholder(String, Spec),
+    Optional(Vec<Segment>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Spec {
+    fill: char,
+    align: Align,
+    width: usize,
+    precision: Option<usize>,
+}
+
+impl Default for Spec {
+    fn default() -> Self {
+        Self {
+            fill: ' ',
+            align: Align::Left,
+            width: 0,
+            precision: None,
+        }
+    }
+}
+
+/// Supplies placeholder values and styling while rendering a template
+pub trait TemplateContext {
+    /// Plain text for a placeholder, or `None` if it has no value
+    fn resolve(&self, name: &str) -> Option<String>;
+
+    /// Apply styling to an already formatted placeholder value
+    fn paint(&self, _name: &str, text: &str) -> String {
+        text.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut chars = source.chars().peekable();
+        let segments = parse_segments(&mut chars, false)?;
+        Ok(Self { segments })
+    }
+
+    pub fn render(&self, context: &dyn TemplateContext) -> String {
+        render_segments(&self.segments, context).0
+    }
+
+    /// Render the template, or `None` if it has placeholders and none of them
+    /// has a value
+    pub fn render_if_any(&self, context: &dyn TemplateContext) -> Option<String> {
+        let placeholders = self.placeholders();
+        let any = placeholders.is_empty()
+            || placeholders
+                .iter()
+                .any(|name| context.resolve(name).is_some_and(|v| !v.is_empty()));
+
+        any.then(|| self.render(context))
+    }
+
+    /// Names of all placeholders in the template
+    pub fn placeholders(&self) -> Vec<&str> {
+        fn collect<'a>(segments: &'a [Segment], names: &mut Vec<&'a str>) {
+            for segment in segments {
+                match segment {
+                    Segment::Placeholder(name, _) => names.push(name),
+                    Segment::Optional(inner) => collect(inner, names),
+                    Segment::Literal(_) => {}
+                }
+            }
+        }
+
+        let mut names = Vec::new();
+        collect(&self.segments, &mut names);
+        names
+    }
+
+    /// Whether the template contains a placeholder with the given name
+    pub fn references(&self, name: &str) -> bool {
+        self.placeholders().contains(&name)
+    }
+}
+
+fn parse_segments(
+    chars: &mut std::iter::Peekable<std::str::Chars>,
+    in_optional: bool,
+) -> Result<Vec<Segment>> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '[' | '}' | ']' if chars.peek() == Some(&c) => {
+                chars.next();
+                literal.push(c);
+            }
+            '{' => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => inner.push(c),
+                        None => bail!("Unclosed '{{' in template"),
+                    }
+                }
+                flush_literal(&mut literal, &mut segments);
+                segments.push(parse_placeholder(&inner)?);
+            }
+            '[' => {
+                flush_literal(&mut literal, &mut segments);
+                segments.push(Segment::Optional(parse_segments(chars, true)?));
+            }
+            ']' if in_optional => {
+                flush_literal(&mut literal, &mut segments);
+                return Ok(segments);
+            }
+            '}' | ']' => bail!("Unmatched '{}' in template", c),
+            c => literal.push(c),
+        }
+    }
+
+    if in_optional {
+        bail!("Unclosed '[' in template");
+    }
+
+    flush_literal(&mut literal, &mut segments);
+    Ok(segments)
+}
+
+fn flush_literal(literal: &mut String, segments: &mut Vec<Segment>) {
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(std::mem::take(literal)));
+    }
+}
+
+fn parse_placeholder(inner: &str) -> Result<Segment> {
+    let (name, spec) = match inner.split_once(':') {
+        Some((name, spec)) => (name.trim(), parse_spec(spec)?),
+        None => (inner.trim(), Spec::default()),
+    };
+
+    if name.is_empty() {
+        bail!("Empty placeholder in template");
+    }
+
+    Ok(Segment::Placeholder(name.to_string(), spec))
+}
+
+fn parse_spec(spec: &str) -> Result<Spec> {
+    let mut result = Spec::default();
+    let chars: Vec<char> = spec.chars().collect();
+    let align_of = |c: char| match c {
+        '<' => Some(Align::Left),
+        '>' => Some(Align::Right),
+        '^' => Some(Align::Center),
+        _ => None,
+    };
+
+    let mut pos = 0;
+    if let Some(align) = chars.get(1).and_then(|&c| align_of(c)) {
+        result.fill = chars[0];
+        result.align = align;
+        pos = 2;
+    } else if let Some(align) = chars.first().and_then(|&c| align_of(c)) {
+        result.align = align;
+        pos = 1;
+    }
+
+    let rest: String = chars[pos..].iter().collect();
+    let (width, precision) = match rest.split_once('.') {
+        Some((width, precision)) => (width, Some(precision)),
+        None => (rest.as_str(), None),
+    };
+
+    if !width.is_empty() {
+        result.width = width
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid width '{}' in template", width))?;
+    }
+    if let Some(precision) = precision {
+        result.precision = Some(
+            precision
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid precision '{}' in template", precision))?,
+        );
+    }
+
+    Ok(result)
+}
+
+/// Render segments, also reporting whether every placeholder had a value
+fn render_segments(segments: &[Segment], context: &dyn TemplateContext) -> (String, bool) {
+    let mut output = String::new();
+    let mut complete = true;
+
+    for segment in segments {
+        match segment {
+            Segment::Literal(text) => output.push_str(text),
+            Segment::Placeholder(name, spec) => {
+                match context.resolve(name).filter(|v| !v.is_empty()) {
+                    Some(value) => {
+                        output.push_str(&context.paint(name, &apply_spec(&value, spec)));
+                    }
+                    None => {
+                        complete = false;
+                        output.push_str(&apply_spec("", spec));
+                    }
+                }
+            }
+            Segment::Optional(inner) => {
+                if let (text, true) = render_segments(inner, context) {
+                    output.push_str(&text);
+                }
+            }
+        }
+    }
+
+    (output, complete)
+}
+
+fn apply_spec(value: &str, spec: &Spec) -> String {
+    let value = match spec.precision {
+        Some(precision) => match value.parse::<f64>() {
+            Ok(number) => format!("{:.*}", precision, number),
+            Err(_) => value.chars().take(precision).collect(),
+        },
+        None => value.to_string(),
+    };
+
+    let padding = spec.width.saturating_sub(display_width(&value));
+    if padding == 0 {
+        return value;
+    }
+
+    let fill = |n: usize| spec.fill.to_string().repeat(n);
+    match spec.align {
+        Align::Left => format!("{}{}", value, fill(padding)),
+        Align::Right => format!("{}{}", fill(padding), value),
+        Align::Center => format!(
+            "{}{}{}",
+            fill(padding / 2),
+            value,
+            fill(padding - padding / 2)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct Values(HashMap<&'static str, &'static str>);
+
+    impl TemplateContext for Values {
+        fn resolve(&self, name: &str) -> Option<String> {
+            self.0.get(name).map(|v| v.to_string())
+        }
+    }
+
+    fn render(template: &str, values: &[(&'static str, &'static str)]) -> String {
+        let context = Values(values.iter().copied().collect());
+        Template::parse(template).unwrap().render(&context)
+    }
+
+    #[test]
+    fn test_basic_placeholders() {
+        let values = [("label", "OS"), ("sep", ":"), ("value", "Linux")];
+        assert_eq!(render("{label}{sep} {value}", &values), "OS: Linux");
+        assert_eq!(render("{{{value}}}", &values), "{Linux}");
+    }
+
+    #[test]
+    fn test_alignment_and_padding() {
+        let values = [("label", "OS")];
+        assert_eq!(render("{label:>5}|", &values), "   OS|");
+        assert_eq!(render("{label:<5}|", &values), "OS   |");
+        assert_eq!(render("{label:.^6}|", &values), "..OS..|");
+    }
+
+    #[test]
+    fn test_precision() {
+        let values = [("memory.percent", "42.567"), ("cpu", "Ryzen 9")];
+        assert_eq!(render("{memory.percent:.0}%", &values), "43%");
+        assert_eq!(render("{cpu:.5}", &values), "Ryzen");
+    }
+
+    #[test]
+    fn test_optional_segments() {
+        let values = [("value", "8 GB"), ("memory.percent", "50")];
+        assert_eq!(
+            render("{value}[ ({memory.percent}%)]", &values),
+            "8 GB (50%)"
+        );
+        assert_eq!(render("{value}[ ({missing}%)]", &values), "8 GB");
+        assert_eq!(render("{missing}{value}", &values), "8 GB");
+        assert_eq!(render("[[{value}]]", &values), "[8 GB]");
+    }
+
+    #[test]
+    fn test_render_if_any() {
+        let values = [("uptime", "3 hours")];
+        let context = Values(values.iter().copied().collect());
+        let render = |template: &str| Template::parse(template).unwrap().render_if_any(&context);
+
+        assert_eq!(render("Up for {uptime}").as_deref(), Some("Up for 3 hours"));
+        assert_eq!(
+            render("Up for {uptime}{missing}").as_deref(),
+            Some("Up for 3 hours")
+        );
+        assert_eq!(render("Booted {missing}"), None);
+        assert_eq!(render("Hello").as_deref(), Some("Hello"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Template::parse("{value").is_err());
+        assert!(Template::parse("[{value}").is_err());
+        assert!(Template::parse("value}").is_err());
+        assert!(Template::parse("{value:abc}").is_err());
+    }
+}
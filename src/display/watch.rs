@@ -0,0 +1,90 @@
+use super::Display;
+use crate::info::{InfoCollector, VOLATILE_FIELDS};
+use crate::utils::terminal::terminal_size;
+use anyhow::{Context, Result};
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often Ctrl-C and terminal resizes are checked between refreshes
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Switches to the alternate screen and restores the terminal when dropped,
+/// including when rendering fails
+struct AlternateScreen;
+
+impl AlternateScreen {
+    fn enter() -> Result<Self> {
+        // Alternate screen, hidden cursor
+        print!("\x1b[?1049h\x1b[?25l");
+        io::stdout()
+            .flush()
+            .context("Failed to write to terminal")?;
+        Ok(Self)
+    }
+}
+
+impl Drop for AlternateScreen {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+    }
+}
+
+/// Redraw the display in place every `interval` until Ctrl-C, re-collecting
+/// only volatile fields and keeping the rest from the first collection
+pub fn run(display: &Display, collector: &InfoCollector, interval: Duration) -> Result<()> {
+    let running = Arc::new(AtomicBool::new(true));
+    let handler_flag = running.clone();
+    ctrlc::set_handler(move || handler_flag.store(false, Ordering::SeqCst))
+        .context("Failed to set Ctrl-C handler")?;
+
+    let mut info = collector.collect_all()?;
+    let _screen = AlternateScreen::enter()?;
+
+    while running.load(Ordering::SeqCst) {
+        let size = terminal_size();
+        draw(&display.render(&info)?, size)?;
+
+        // Wait for the next refresh, redrawing early on resize
+        let started = Instant::now();
+        let mut resized = false;
+        while running.load(Ordering::SeqCst) && started.elapsed() < interval {
+            thread::sleep(POLL_INTERVAL);
+            if terminal_size() != size {
+                resized = true;
+                break;
+            }
+        }
+
+        if !resized {
+            collector.refresh(&mut info, VOLATILE_FIELDS);
+        }
+    }
+
+    Ok(())
+}
+
+fn draw(output: &str, size: Option<(u16, u16)>) -> Result<()> {
+    let rows = size.map(|(_, rows)| rows as usize).unwrap_or(usize::MAX);
+    let mut frame = String::from("\x1b[H");
+
+    // Overwrite in place and clear leftovers instead of clearing the whole
+    // screen first, which flickers. Lines past the bottom would scroll.
+    for (i, line) in output.lines().take(rows).enumerate() {
+        if i > 0 {
+            frame.push('\n');
+        }
+        frame.push_str(line);
+        frame.push_str("\x1b[K");
+    }
+    frame.push_str("\x1b[J");
+
+    let mut stdout = io::stdout().lock();
+    stdout
+        .write_all(frame.as_bytes())
+        .and_then(|_| stdout.flush())
+        .context("Failed to write to terminal")
+}
@@ -0,0 +1,529 @@
+use super::FieldValue;
+use crate::config::CpuConfig;
+use crate::utils::cgroup;
+use anyhow::{bail, Result};
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use std::path::Path;
+use sysinfo::System;
+
+#[cfg(target_os = "linux")]
+const SYS_CPU: &str = "/sys/devices/system/cpu";
+
+/// Processor layout and capabilities
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CpuTopology {
+    pub brand: String,
+    pub sockets: usize,
+    /// Physical cores across all sockets
+    pub cores: usize,
+    /// Logical processors (hardware threads)
+    pub threads: usize,
+    /// Physical core counts on hybrid CPUs as (performance, efficiency)
+    pub hybrid: Option<(usize, usize)>,
+    /// Total size in bytes per cache, keyed by name (L1d, L1i, L2, L3)
+    pub caches: BTreeMap<String, u64>,
+    pub min_mhz: Option<u64>,
+    pub max_mhz: Option<u64>,
+    pub current_mhz: Option<u64>,
+}
+
+/// One `processor` block of /proc/cpuinfo
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CpuInfoEntry {
+    processor: usize,
+    model_name: Option<String>,
+    physical_id: Option<usize>,
+    core_id: Option<usize>,
+    mhz: Option<f64>,
+}
+
+/// Clock range of one logical CPU from cpufreq
+#[cfg(target_os = "linux")]
+struct CpuClock {
+    min_mhz: Option<u64>,
+    max_mhz: Option<u64>,
+    current_mhz: Option<u64>,
+}
+
+pub fn get_cpu_info(config: &CpuConfig) -> Result<FieldValue> {
+    let topology = read_topology()?;
+    let brand = clean_brand(&topology.brand, config);
+
+    let mut cores = match topology.hybrid {
+        Some((performance, efficiency)) => {
+            format!("{}P + {}E cores", performance, efficiency)
+        }
+        None => plural(topology.cores, "core"),
+    };
+    if topology.threads != topology.cores {
+        cores.push_str(&format!(", {}", plural(topology.threads, "thread")));
+    }
+    // A container's CPU quota, when it's below the number of threads
+    let limit = config
+        .cgroup_limits
+        .then(cgroup::cpu_limit)
+        .flatten()
+        .filter(|&limit| limit < topology.threads as f64)
+        .map(|limit| {
+            format!("{:.2}", limit)
+                .trim_end_matches('0')
+                .trim_end_matches('.')
+                .to_string()
+        });
+    if let Some(limit) = &limit {
+        let unit = if limit == "1" { "CPU" } else { "CPUs" };
+        cores.push_str(&format!(", limited to {} {}", limit, unit));
+    }
+
+    let mut value = if topology.sockets > 1 {
+        format!("{} x {} ({})", topology.sockets, brand, cores)
+    } else {
+        format!("{} ({})", brand, cores)
+    };
+    if let Some(mhz) = topology.max_mhz.or(topology.current_mhz) {
+        value.push_str(&format!(" @ {:.2} GHz", mhz as f64 / 1000.0));
+    }
+
+    let mut field = FieldValue::from(value)
+        .with_part("brand", brand)
+        .with_part("sockets", topology.sockets)
+        .with_part("cores", topology.cores)
+        .with_part("threads", topology.threads);
+    if let Some(limit) = limit {
+        field = field.with_part("limit", limit);
+    }
+
+    if let Some((performance, efficiency)) = topology.hybrid {
+        field = field
+            .with_part("performance_cores", performance)
+            .with_part("efficiency_cores", efficiency);
+    }
+    for (part, mhz) in [
+        ("frequency", topology.current_mhz),
+        ("frequency_min", topology.min_mhz),
+        ("frequency_max", topology.max_mhz),
+    ] {
+        if let Some(mhz) = mhz {
+            field = field.with_part(part, mhz);
+        }
+    }
+    for (part, name) in [("l1d", "L1d"), ("l1i", "L1i"), ("l2", "L2"), ("l3", "L3")] {
+        if let Some(size) = topology.caches.get(name) {
+            field = field.with_part(part, format_cache_size(*size));
+        }
+    }
+
+    Ok(field)
+}
+
+pub fn get_cpu_cache() -> Result<FieldValue> {
+    let topology = read_topology()?;
+    if topology.caches.is_empty() {
+        bail!("CPU cache information not available");
+    }
+
+    let value = topology
+        .caches
+        .iter()
+        .map(|(name, size)| format!("{} {}", name, format_cache_size(*size)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Ok(value.into())
+}
+
+/// Remove the configured marketing noise from a CPU brand string
+pub fn clean_brand(brand: &str, config: &CpuConfig) -> String {
+    let mut brand = brand.trim().to_string();
+
+    if config.brand_strip_suffix {
+        // "... CPU @ 2.40GHz"
+        if let Some(at) = brand.find(" @ ") {
+            brand.truncate(at);
+        }
+        // "... 8-Core Processor"
+        let words: Vec<&str> = brand.split_whitespace().collect();
+        if let Some(pos) = words.iter().position(|word| is_core_count(word)) {
+            if words[pos + 1..].iter().all(|word| *word == "Processor") {
+                brand = words[..pos].join(" ");
+            }
+        }
+    }
+
+    for pattern in &config.brand_remove {
+        brand = brand.replace(pattern.as_str(), " ");
+    }
+
+    brand.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+fn is_core_count(word: &str) -> bool {
+    word.strip_suffix("-Core")
+        .or_else(|| word.strip_suffix("-core"))
+        .is_some_and(|count| !count.is_empty() && count.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+fn format_cache_size(bytes: u64) -> String {
+    const MIB: u64 = 1024 * 1024;
+    if bytes >= MIB && bytes.is_multiple_of(MIB / 4) {
+        format!("{} MiB", bytes as f64 / MIB as f64)
+    } else {
+        format!("{} KiB", bytes / 1024)
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn read_topology() -> Result<CpuTopology> {
+    let entries = parse_cpuinfo(&fs::read_to_string("/proc/cpuinfo").unwrap_or_default());
+    let online = fs::read_to_string(Path::new(SYS_CPU).join("online"))
+        .map(|list| parse_cpu_list(&list))
+        .unwrap_or_else(|_| entries.iter().map(|entry| entry.processor).collect());
+    if online.is_empty() {
+        return sysinfo_topology();
+    }
+
+    // Topology from sysfs covers architectures whose cpuinfo lacks core ids
+    let mut core_ids = BTreeMap::new();
+    for &cpu in &online {
+        let topology = Path::new(SYS_CPU).join(format!("cpu{}/topology", cpu));
+        let entry = entries.iter().find(|entry| entry.processor == cpu);
+        let package = read_number(&topology.join("physical_package_id"))
+            .or_else(|| entry.and_then(|entry| entry.physical_id))
+            .unwrap_or(0);
+        let core = read_number(&topology.join("core_id"))
+            .or_else(|| entry.and_then(|entry| entry.core_id))
+            .unwrap_or(cpu);
+        core_ids.insert(cpu, (package, core));
+    }
+
+    let sockets: BTreeSet<usize> = core_ids.values().map(|(package, _)| *package).collect();
+    let cores: BTreeSet<(usize, usize)> = core_ids.values().copied().collect();
+
+    let clocks: Vec<CpuClock> = online
+        .iter()
+        .map(|&cpu| {
+            let cpufreq = Path::new(SYS_CPU).join(format!("cpu{}/cpufreq", cpu));
+            let mhz = |name: &str| read_number(&cpufreq.join(name)).map(|khz| khz as u64 / 1000);
+            CpuClock {
+                min_mhz: mhz("cpuinfo_min_freq"),
+                max_mhz: mhz("cpuinfo_max_freq"),
+                current_mhz: mhz("scaling_cur_freq"),
+            }
+        })
+        .collect();
+
+    let current_mhz = clocks
+        .iter()
+        .filter_map(|clock| clock.current_mhz)
+        .max()
+        .or_else(|| {
+            entries
+                .iter()
+                .filter_map(|entry| entry.mhz)
+                .fold(None, |max: Option<f64>, mhz| {
+                    Some(max.map_or(mhz, |max| max.max(mhz)))
+                })
+                .map(|mhz| mhz.round() as u64)
+        });
+
+    // Intel hybrid CPUs expose separate PMUs for P-cores and E-cores; ARM
+    // big.LITTLE ranks its cores by capacity instead. Maximum clocks alone
+    // differ between otherwise identical cores (Turbo Boost Max 3.0, CPPC).
+    let count_cores = |cpus: &[usize]| {
+        cpus.iter()
+            .filter_map(|cpu| core_ids.get(cpu))
+            .collect::<BTreeSet<_>>()
+            .len()
+    };
+    let hybrid = match (
+        fs::read_to_string("/sys/devices/cpu_core/cpus"),
+        fs::read_to_string("/sys/devices/cpu_atom/cpus"),
+    ) {
+        (Ok(performance), Ok(efficiency)) => Some((
+            count_cores(&parse_cpu_list(&performance)),
+            count_cores(&parse_cpu_list(&efficiency)),
+        )),
+        _ => {
+            let capacities: Vec<(usize, usize)> = online
+                .iter()
+                .filter_map(|&cpu| {
+                    let path = Path::new(SYS_CPU).join(format!("cpu{}/cpu_capacity", cpu));
+                    Some((cpu, read_number(&path)?))
+                })
+                .collect();
+            split_by_capacity(&capacities).map(|(performance, efficiency)| {
+                (count_cores(&performance), count_cores(&efficiency))
+            })
+        }
+    };
+
+    Ok(CpuTopology {
+        brand: entries
+            .iter()
+            .find_map(|entry| entry.model_name.clone())
+            .unwrap_or_else(sysinfo_brand),
+        sockets: sockets.len().max(1),
+        cores: cores.len(),
+        threads: online.len(),
+        hybrid: hybrid.filter(|(performance, efficiency)| *performance > 0 && *efficiency > 0),
+        caches: read_caches(&online),
+        min_mhz: clocks.iter().filter_map(|clock| clock.min_mhz).min(),
+        max_mhz: clocks.iter().filter_map(|clock| clock.max_mhz).max(),
+        current_mhz,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_topology() -> Result<CpuTopology> {
+    sysinfo_topology()
+}
+
+/// Sum cache sizes over every distinct cache instance
+#[cfg(target_os = "linux")]
+fn read_caches(cpus: &[usize]) -> BTreeMap<String, u64> {
+    let mut seen = BTreeSet::new();
+    let mut caches = BTreeMap::new();
+
+    for cpu in cpus {
+        let Ok(indexes) = fs::read_dir(Path::new(SYS_CPU).join(format!("cpu{}/cache", cpu))) else {
+            continue;
+        };
+
+        for index in indexes.flatten() {
+            let path = index.path();
+            let read = |name: &str| {
+                fs::read_to_string(path.join(name))
+                    .map(|value| value.trim().to_string())
+                    .ok()
+            };
+            let (Some(level), Some(kind), Some(size)) = (read("level"), read("type"), read("size"))
+            else {
+                continue;
+            };
+            let Some(size) = parse_cache_size(&size) else {
+                continue;
+            };
+
+            let name = match kind.as_str() {
+                "Data" => format!("L{}d", level),
+                "Instruction" => format!("L{}i", level),
+                _ => format!("L{}", level),
+            };
+            // Shared caches appear under every CPU that uses them
+            let shared = read("shared_cpu_list").unwrap_or_else(|| cpu.to_string());
+            if seen.insert((name.clone(), shared)) {
+                *caches.entry(name).or_insert(0) += size;
+            }
+        }
+    }
+
+    caches
+}
+
+#[cfg(target_os = "linux")]
+fn read_number(path: &Path) -> Option<usize> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Split CPUs into performance and efficiency CPUs by their `cpu_capacity`,
+/// where the least capable ones are the efficiency CPUs. `None` when all CPUs
+/// are alike.
+#[cfg(any(target_os = "linux", test))]
+fn split_by_capacity(capacities: &[(usize, usize)]) -> Option<(Vec<usize>, Vec<usize>)> {
+    let lowest = capacities.iter().map(|(_, capacity)| *capacity).min()?;
+    let (efficiency, performance): (Vec<_>, Vec<_>) = capacities
+        .iter()
+        .partition(|(_, capacity)| *capacity == lowest);
+    let cpus = |list: Vec<&(usize, usize)>| list.iter().map(|(cpu, _)| *cpu).collect::<Vec<_>>();
+    (!performance.is_empty()).then(|| (cpus(performance), cpus(efficiency)))
+}
+
+fn sysinfo_topology() -> Result<CpuTopology> {
+    let mut sys = System::new();
+    sys.refresh_cpu_all();
+
+    let cpus = sys.cpus();
+    if cpus.is_empty() {
+        bail!("No CPU information available");
+    }
+
+    Ok(CpuTopology {
+        brand: cpus[0].brand().to_string(),
+        sockets: 1,
+        cores: System::physical_core_count().unwrap_or(cpus.len()),
+        threads: cpus.len(),
+        current_mhz: cpus
+            .iter()
+            .map(|cpu| cpu.frequency())
+            .max()
+            .filter(|mhz| *mhz > 0),
+        ..Default::default()
+    })
+}
+
+fn sysinfo_brand() -> String {
+    let mut sys = System::new();
+    sys.refresh_cpu_all();
+    sys.cpus()
+        .first()
+        .map(|cpu| cpu.brand().to_string())
+        .unwrap_or_else(|| "Unknown CPU".to_string())
+}
+
+fn parse_cpuinfo(content: &str) -> Vec<CpuInfoEntry> {
+    let mut entries = Vec::new();
+    let mut entry: Option<CpuInfoEntry> = None;
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+
+        match key {
+            "processor" => {
+                entries.extend(entry.take());
+                entry = Some(CpuInfoEntry {
+                    processor: value.parse().unwrap_or(entries.len()),
+                    ..Default::default()
+                });
+            }
+            // "model name" on x86, "Model" or "cpu model" elsewhere
+            "model name" | "cpu model" | "Model" => {
+                if let Some(entry) = entry.as_mut() {
+                    entry.model_name.get_or_insert_with(|| value.to_string());
+                }
+            }
+            "physical id" => {
+                if let Some(entry) = entry.as_mut() {
+                    entry.physical_id = value.parse().ok();
+                }
+            }
+            "core id" => {
+                if let Some(entry) = entry.as_mut() {
+                    entry.core_id = value.parse().ok();
+                }
+            }
+            "cpu MHz" => {
+                if let Some(entry) = entry.as_mut() {
+                    entry.mhz = value.parse().ok();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    entries.extend(entry);
+    entries
+}
+
+/// Parse a kernel CPU list such as `0-3,8,10-11`
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    list.trim()
+        .split(',')
+        .filter_map(|range| match range.split_once('-') {
+            Some((start, end)) => Some((start.parse().ok()?..=end.parse().ok()?).collect()),
+            None => range.parse().ok().map(|cpu| vec![cpu]),
+        })
+        .flatten()
+        .collect()
+}
+
+/// Parse a sysfs cache size such as `48K` or `2M` into bytes
+fn parse_cache_size(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let (number, multiplier) = match size.chars().last()? {
+        'K' => (&size[..size.len() - 1], 1024),
+        'M' => (&size[..size.len() - 1], 1024 * 1024),
+        'G' => (&size[..size.len() - 1], 1024 * 1024 * 1024),
+        _ => (size, 1),
+    };
+    number.parse::<u64>().ok().map(|number| number * multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpuinfo() {
+        let content = "processor\t: 0\nmodel name\t: Intel(R) Core(TM) i7\nphysical id\t: 0\n\
+                       core id\t\t: 0\ncpu MHz\t\t: 2400.000\n\n\
+                       processor\t: 1\nmodel name\t: Intel(R) Core(TM) i7\nphysical id\t: 0\n\
+                       core id\t\t: 0\ncpu MHz\t\t: 3100.5\n";
+        let entries = parse_cpuinfo(content);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].processor, 1);
+        assert_eq!(entries[1].core_id, Some(0));
+        assert_eq!(entries[1].mhz, Some(3100.5));
+        assert_eq!(
+            entries[0].model_name.as_deref(),
+            Some("Intel(R) Core(TM) i7")
+        );
+    }
+
+    #[test]
+    fn test_parse_cpu_list_and_cache_size() {
+        assert_eq!(parse_cpu_list("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cache_size("48K"), Some(48 * 1024));
+        assert_eq!(parse_cache_size("2M"), Some(2 * 1024 * 1024));
+        assert_eq!(format_cache_size(1280 * 1024), "1.25 MiB");
+        assert_eq!(format_cache_size(48 * 1024), "48 KiB");
+    }
+
+    #[test]
+    fn test_split_by_capacity() {
+        // Cortex-X3 + 3x A715 + 4x A510
+        let capacities = [
+            (0, 400),
+            (1, 400),
+            (2, 400),
+            (3, 400),
+            (4, 870),
+            (5, 870),
+            (6, 870),
+            (7, 1024),
+        ];
+        assert_eq!(
+            split_by_capacity(&capacities),
+            Some((vec![4, 5, 6, 7], vec![0, 1, 2, 3]))
+        );
+        assert_eq!(split_by_capacity(&[(0, 1024), (1, 1024)]), None);
+        assert_eq!(split_by_capacity(&[]), None);
+    }
+
+    #[test]
+    fn test_clean_brand() {
+        let config = CpuConfig::default();
+        assert_eq!(
+            clean_brand("Intel(R) Core(TM) i7-8550U CPU @ 1.80GHz", &config),
+            "Intel Core i7-8550U"
+        );
+        assert_eq!(
+            clean_brand("AMD Ryzen 7 5800X 8-Core Processor", &config),
+            "AMD Ryzen 7 5800X"
+        );
+        assert_eq!(clean_brand("Apple M2", &config), "Apple M2");
+
+        let config = CpuConfig {
+            brand_remove: Vec::new(),
+            brand_strip_suffix: false,
+            ..CpuConfig::default()
+        };
+        assert_eq!(
+            clean_brand("Intel(R) Xeon(R)  CPU @ 2.40GHz", &config),
+            "Intel(R) Xeon(R) CPU @ 2.40GHz"
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use super::FieldValue;
+use crate::config::{DiskConfig, DiskFormat, UnitsConfig};
+use crate::utils::units::format_size;
+use anyhow::{bail, Result};
+use std::collections::HashSet;
+use sysinfo::Disks;
+
+/// A mounted filesystem
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mount {
+    pub mount_point: String,
+    pub device: String,
+    pub fs_type: String,
+    pub removable: bool,
+    pub total: u64,
+    pub available: u64,
+}
+
+/// The `disk` field: one `disk:<mount>` child per mount that passes the
+/// filters. The field's own value is the first mount's, usually `/`.
+pub fn get_disk_info(config: &DiskConfig, units: &UnitsConfig) -> Result<FieldValue> {
+    let mounts = list_mounts(config);
+    let Some(first) = mounts.first() else {
+        bail!("No disks found");
+    };
+
+    let mut field = mount_value(first, config, units);
+    for mount in &mounts {
+        field = field.with_child(
+            format!("disk:{}", mount.mount_point),
+            mount_value(mount, config, units),
+        );
+    }
+
+    Ok(field)
+}
+
+/// A single mount, for `disk:<mount>` entries in the field list
+pub fn get_mount_info(
+    mount_point: &str,
+    config: &DiskConfig,
+    units: &UnitsConfig,
+) -> Result<FieldValue> {
+    match read_mounts()
+        .iter()
+        .find(|mount| mount.mount_point == mount_point)
+    {
+        Some(mount) => Ok(mount_value(mount, config, units)),
+        None => bail!("{} is not mounted", mount_point),
+    }
+}
+
+/// Mounted filesystems that pass the configured filters, sorted by mount point
+pub fn list_mounts(config: &DiskConfig) -> Vec<Mount> {
+    let mounts = read_mounts();
+    let filtered = filter_mounts(mounts.clone(), config);
+
+    // Filtering everything out (e.g. an overlay root in a container) would
+    // leave the field empty, so fall back to the root filesystem
+    if filtered.is_empty() && config.include_mounts.is_empty() {
+        return mounts
+            .into_iter()
+            .filter(|mount| mount.mount_point == "/" && mount.total > 0)
+            .collect();
+    }
+    filtered
+}
+
+fn read_mounts() -> Vec<Mount> {
+    Disks::new_with_refreshed_list()
+        .iter()
+        .map(|disk| Mount {
+            mount_point: disk.mount_point().to_string_lossy().to_string(),
+            device: disk.name().to_string_lossy().to_string(),
+            fs_type: disk.file_system().to_string_lossy().to_string(),
+            removable: disk.is_removable(),
+            total: disk.total_space(),
+            available: disk.available_space(),
+        })
+        .collect()
+}
+
+fn filter_mounts(mut mounts: Vec<Mount>, config: &DiskConfig) -> Vec<Mount> {
+    let allowed = |patterns: &[String], value: &str, default: bool| {
+        if patterns.is_empty() {
+            default
+        } else {
+            patterns
+                .iter()
+                .any(|pattern| matches_pattern(value, pattern))
+        }
+    };
+
+    mounts.retain(|mount| {
+        mount.total > 0
+            && (config.show_removable || !mount.removable)
+            && allowed(&config.include_mounts, &mount.mount_point, true)
+            && !allowed(&config.exclude_mounts, &mount.mount_point, false)
+            && allowed(&config.include_fstypes, &mount.fs_type, true)
+            && !allowed(&config.exclude_fstypes, &mount.fs_type, false)
+    });
+
+    if config.dedupe {
+        // Bind mounts and subvolumes share a device; keep its shortest mount point
+        mounts.sort_by(|a, b| {
+            (a.mount_point.len(), &a.mount_point).cmp(&(b.mount_point.len(), &b.mount_point))
+        });
+        let mut seen = HashSet::new();
+        mounts.retain(|mount| seen.insert(mount.device.clone()));
+    }
+
+    mounts.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+    mounts
+}
+
+/// Exact match, or prefix match for patterns ending in `*`
+fn matches_pattern(value: &str, pattern: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => value.starts_with(prefix),
+        None => value == pattern,
+    }
+}
+
+fn mount_value(mount: &Mount, config: &DiskConfig, units: &UnitsConfig) -> FieldValue {
+    let used_bytes = mount.total.saturating_sub(mount.available);
+    let percent = used_bytes as f64 / mount.total as f64 * 100.0;
+
+    let used = format_size(used_bytes, units);
+    let total = format_size(mount.total, units);
+    let free = format_size(mount.available, units);
+
+    let value = match config.format {
+        DiskFormat::Usage => format!("{} / {} ({:.1}%)", used, total, percent),
+        DiskFormat::Used => format!("{} used of {}", used, total),
+        DiskFormat::Free => format!("{} free of {}", free, total),
+        DiskFormat::Percent => format!("{:.1}%", percent),
+    };
+
+    FieldValue::from(value)
+        .with_part("used", used)
+        .with_part("total", total)
+        .with_part("free", free)
+        .with_part("percent", format!("{:.1}", percent))
+        .with_part("mount", &mount.mount_point)
+        .with_part("device", &mount.device)
+        .with_part("fstype", &mount.fs_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mount(mount_point: &str, device: &str, fs_type: &str) -> Mount {
+        Mount {
+            mount_point: mount_point.to_string(),
+            device: device.to_string(),
+            fs_type: fs_type.to_string(),
+            removable: false,
+            total: 100,
+            available: 50,
+        }
+    }
+
+    fn mount_points(mounts: &[Mount]) -> Vec<&str> {
+        mounts.iter().map(|m| m.mount_point.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filter_mounts() {
+        let mounts = vec![
+            mount("/home", "/dev/sda2", "ext4"),
+            mount("/", "/dev/sda1", "ext4"),
+            mount("/var/lib/docker/btrfs", "/dev/sda1", "ext4"),
+            mount("/snap/core/1", "/dev/loop0", "squashfs"),
+            mount("/run", "tmpfs", "tmpfs"),
+            Mount {
+                removable: true,
+                ..mount("/media/usb", "/dev/sdb1", "vfat")
+            },
+        ];
+
+        let config = DiskConfig::default();
+        assert_eq!(
+            mount_points(&filter_mounts(mounts.clone(), &config)),
+            vec!["/", "/home", "/media/usb"]
+        );
+
+        let config = DiskConfig {
+            show_removable: false,
+            exclude_mounts: vec!["/home".to_string()],
+            exclude_fstypes: Vec::new(),
+            dedupe: false,
+            ..DiskConfig::default()
+        };
+        assert_eq!(
+            mount_points(&filter_mounts(mounts.clone(), &config)),
+            vec!["/", "/run", "/snap/core/1", "/var/lib/docker/btrfs"]
+        );
+
+        let config = DiskConfig {
+            include_mounts: vec!["/snap/*".to_string(), "/run".to_string()],
+            exclude_fstypes: Vec::new(),
+            ..DiskConfig::default()
+        };
+        assert_eq!(
+            mount_points(&filter_mounts(mounts, &config)),
+            vec!["/run", "/snap/core/1"]
+        );
+    }
+}
@@ -1,71 +1,181 @@
-use anyhow::Result;
-use sysinfo::{Disks, System};
+use super::FieldValue;
+use crate::config::CpuConfig;
+use anyhow::{bail, Context, Result};
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "macos")]
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+use sysinfo::{Components, System, MINIMUM_CPU_UPDATE_INTERVAL};
 
-pub fn get_cpu_info() -> Result<String> {
+/// Parts of sensor labels that measure the CPU package as a whole, most
+/// specific first, e.g. "coretemp Package id 0" or "k10temp Tctl"
+const CPU_SENSORS: &[&str] = &[
+    "Package id",
+    "Tctl",
+    "Tdie",
+    "cpu_thermal",
+    "coretemp",
+    "k10temp",
+];
+
+/// Sparkline levels for per-core CPU usage, lowest first
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Refresh CPU usage twice, `window` apart. A single refresh has nothing to
+/// compare against, so `cpu_usage()` would read 0.
+pub fn sample_cpu_usage(sys: &mut System, window: Duration) {
+    sys.refresh_cpu_usage();
+    thread::sleep(window.max(MINIMUM_CPU_UPDATE_INTERVAL));
+    sys.refresh_cpu_usage();
+}
+
+pub fn get_cpu_usage(config: &CpuConfig) -> Result<FieldValue> {
     let mut sys = System::new();
-    sys.refresh_cpu_all();
+    sample_cpu_usage(&mut sys, Duration::from_millis(config.sample_ms));
 
-    let cpus = sys.cpus();
-    if cpus.is_empty() {
-        return Ok("Unknown CPU".to_string());
+    let cores: Vec<f32> = sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
+    if cores.is_empty() {
+        bail!("CPU usage not available");
     }
 
-    let cpu = &cpus[0];
-    let brand = cpu.brand().trim();
-    let frequency = cpu.frequency();
-    let core_count = cpus.len();
+    let usage = sys.global_cpu_usage();
+    let sparkline: String = cores
+        .iter()
+        .map(|usage| SPARK_LEVELS[(usage.clamp(0.0, 100.0) / 100.0 * 7.0).round() as usize])
+        .collect();
 
-    if frequency > 0 {
-        Ok(format!(
-            "{} ({} cores) @ {} MHz",
-            brand, core_count, frequency
-        ))
+    let value = if config.per_core {
+        format!("{:.1}% {}", usage, sparkline)
     } else {
-        Ok(format!("{} ({} cores)", brand, core_count))
+        format!("{:.1}%", usage)
+    };
+
+    Ok(FieldValue::from(value)
+        .with_part("percent", format!("{:.1}", usage))
+        .with_part("cores", sparkline))
+}
+
+pub fn get_battery_info() -> Result<FieldValue> {
+    #[cfg(target_os = "linux")]
+    {
+        for entry in fs::read_dir("/sys/class/power_supply")?.flatten() {
+            let path = entry.path();
+            let read = |name: &str| {
+                fs::read_to_string(path.join(name))
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_default()
+            };
+
+            if read("type") != "Battery" {
+                continue;
+            }
+
+            if let Ok(percent) = read("capacity").parse::<u8>() {
+                return Ok(battery_value(percent, &read("status")));
+            }
+        }
     }
+
+    #[cfg(target_os = "macos")]
+    {
+        // e.g. " -InternalBattery-0 (id=1234)\t85%; charging; 1:02 remaining present: true"
+        if let Ok(output) = Command::new("pmset").args(["-g", "batt"]).output() {
+            let output_str = String::from_utf8_lossy(&output.stdout);
+            if let Some(line) = output_str.lines().find(|line| line.contains('%')) {
+                let mut parts = line.split(';').map(|s| s.trim());
+                let percent = parts
+                    .next()
+                    .and_then(|s| s.rsplit(|c: char| c.is_whitespace()).next())
+                    .and_then(|s| s.trim_end_matches('%').parse::<u8>().ok());
+                if let Some(percent) = percent {
+                    let status = parts.next().unwrap_or("");
+                    return Ok(battery_value(percent, status));
+                }
+            }
+        }
+    }
+
+    anyhow::bail!("No battery found")
 }
 
-pub fn get_memory_info() -> Result<String> {
-    let mut sys = System::new_all();
-    sys.refresh_memory();
+fn battery_value(percent: u8, status: &str) -> FieldValue {
+    let value = if status.is_empty() {
+        format!("{}%", percent)
+    } else {
+        // Normalize status casing, e.g. "charging" -> "Charging"
+        let mut chars = status.chars();
+        let status: String = chars
+            .next()
+            .map(|c| c.to_uppercase().chain(chars).collect())
+            .unwrap_or_default();
+        format!("{}% [{}]", percent, status)
+    };
 
-    let total = sys.total_memory();
-    let used = sys.used_memory();
-    let usage_percent = (used as f64 / total as f64) * 100.0;
+    FieldValue::from(value)
+        .with_part("percent", percent)
+        .with_part("status", status)
+}
 
-    Ok(format!(
-        "{} MB / {} MB ({:.1}%)",
-        used / 1024 / 1024,
-        total / 1024 / 1024,
-        usage_percent
-    ))
+/// The CPU temperature, or the hottest sensor's when there's no CPU sensor
+pub fn get_temperature() -> Result<FieldValue> {
+    let readings: Vec<(String, f32)> = Components::new_with_refreshed_list()
+        .iter()
+        .filter_map(|component| {
+            let celsius = component.temperature().filter(|t| t.is_finite())?;
+            Some((component.label().to_string(), celsius))
+        })
+        .collect();
+    describe_temperature(&readings)
 }
 
-pub fn get_disk_info() -> Result<String> {
-    let disks = Disks::new_with_refreshed_list();
+fn describe_temperature(readings: &[(String, f32)]) -> Result<FieldValue> {
+    let hottest = readings
+        .iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .context("No temperature sensors found")?;
+    let (sensor, celsius) = CPU_SENSORS
+        .iter()
+        .find_map(|key| readings.iter().find(|(label, _)| label.contains(key)))
+        .unwrap_or(hottest);
 
-    if disks.is_empty() {
-        return Ok("No disks found".to_string());
-    }
+    Ok(FieldValue::from(format!("{:.1}°C", celsius))
+        .with_part("celsius", format!("{:.1}", celsius))
+        .with_part("sensor", sensor)
+        .with_part("max", format!("{:.1}", hottest.1))
+        .with_part("max_sensor", &hottest.0))
+}
 
-    let mut total_space = 0u64;
-    let mut total_used = 0u64;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    for disk in &disks {
-        total_space += disk.total_space();
-        total_used += disk.total_space() - disk.available_space();
+    fn readings(sensors: &[(&str, f32)]) -> Vec<(String, f32)> {
+        sensors
+            .iter()
+            .map(|(label, celsius)| (label.to_string(), *celsius))
+            .collect()
     }
 
-    if total_space == 0 {
-        return Ok("Unknown disk usage".to_string());
-    }
+    #[test]
+    fn test_describe_temperature() {
+        let intel = readings(&[
+            ("coretemp Core 0", 51.0),
+            ("coretemp Package id 0", 54.0),
+            ("nvme Composite", 61.5),
+        ]);
+        let field = describe_temperature(&intel).unwrap();
+        assert_eq!(field.value, "54.0°C");
+        assert_eq!(field.part("sensor"), Some("coretemp Package id 0"));
+        assert_eq!(field.part("max"), Some("61.5"));
+        assert_eq!(field.part("max_sensor"), Some("nvme Composite"));
 
-    let usage_percent = (total_used as f64 / total_space as f64) * 100.0;
+        let no_cpu = readings(&[("acpitz temp1", 40.0), ("nvme Composite", 45.25)]);
+        let field = describe_temperature(&no_cpu).unwrap();
+        assert_eq!(field.value, "45.2°C");
+        assert_eq!(field.part("sensor"), Some("nvme Composite"));
 
-    Ok(format!(
-        "{} GB / {} GB ({:.1}%)",
-        total_used / 1024 / 1024 / 1024,
-        total_space / 1024 / 1024 / 1024,
-        usage_percent
-    ))
+        assert!(describe_temperature(&[]).is_err());
+    }
 }
@@ -0,0 +1,175 @@
+use super::terminal::parse_version;
+use super::FieldValue;
+use anyhow::Result;
+#[cfg(target_os = "linux")]
+use anyhow::{bail, Context};
+#[cfg(target_os = "linux")]
+use std::path::Path;
+#[cfg(target_os = "linux")]
+use std::process::Command;
+
+/// Init programs and their display names. `init` itself is ambiguous and
+/// identified by the state its service manager leaves in `/run`.
+#[cfg(any(target_os = "linux", test))]
+const INIT_SYSTEMS: &[(&str, &str)] = &[
+    ("systemd", "systemd"),
+    ("openrc-init", "OpenRC"),
+    ("runit", "runit"),
+    ("s6-svscan", "s6"),
+    ("s6-linux-init", "s6"),
+    ("dinit", "dinit"),
+    ("shepherd", "GNU Shepherd"),
+    ("busybox", "BusyBox"),
+    ("tini", "tini"),
+    ("docker-init", "tini"),
+    ("dumb-init", "dumb-init"),
+    ("catatonit", "catatonit"),
+];
+
+/// Marker directories of service managers run from a generic `init`
+#[cfg(target_os = "linux")]
+const RUN_MARKERS: &[(&str, &str)] = &[
+    ("/run/systemd/system", "systemd"),
+    ("/run/openrc", "OpenRC"),
+    ("/run/runit", "runit"),
+    ("/run/s6", "s6"),
+    ("/run/dinit", "dinit"),
+];
+
+pub fn get_init() -> Result<FieldValue> {
+    let Some(name) = detect_init() else {
+        return Ok("Unknown".to_string().into());
+    };
+
+    let version = init_version(&name);
+    let value = match &version {
+        Some(version) => format!("{} {}", name, version),
+        None => name.clone(),
+    };
+
+    let mut field = FieldValue::from(value).with_part("name", &name);
+    if let Some(version) = version {
+        field = field.with_part("version", version);
+    }
+    Ok(field)
+}
+
+/// PID 1, by its executable (which `/sbin/init` symlinks resolve to) or
+/// its command name when the executable can't be read
+#[cfg(target_os = "linux")]
+pub fn detect_init() -> Option<String> {
+    let process = crate::utils::process::read_process(1)?;
+    let program = process.program();
+    if let Some(name) = init_name(&program).or_else(|| init_name(&process.name)) {
+        return Some(name.to_string());
+    }
+
+    RUN_MARKERS
+        .iter()
+        .find(|(path, _)| Path::new(path).exists())
+        .map(|(_, name)| name.to_string())
+        .or(Some(program))
+}
+
+#[cfg(target_os = "macos")]
+pub fn detect_init() -> Option<String> {
+    Some("launchd".to_string())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn detect_init() -> Option<String> {
+    None
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn init_name(program: &str) -> Option<&'static str> {
+    INIT_SYSTEMS
+        .iter()
+        .find(|(binary, _)| *binary == program)
+        .map(|(_, name)| *name)
+}
+
+fn init_version(name: &str) -> Option<String> {
+    // "systemd 254 (254.5-1-arch)", "openrc (OpenRC) 0.52.1"
+    let (command, args): (&str, &[&str]) = match name {
+        "systemd" => ("systemctl", &["--version"]),
+        "OpenRC" => ("openrc", &["--version"]),
+        "dinit" => ("dinit", &["--version"]),
+        _ => return None,
+    };
+    let output = std::process::Command::new(command)
+        .args(args)
+        .output()
+        .ok()?;
+    parse_version(String::from_utf8_lossy(&output.stdout).lines().next()?)
+}
+
+/// Failed systemd units, e.g. "2 (nginx.service, backup.service)". Fails
+/// on hosts not running systemd, so the field is left out there.
+#[cfg(target_os = "linux")]
+pub fn get_failed_units() -> Result<FieldValue> {
+    if !Path::new("/run/systemd/system").exists() {
+        bail!("systemd is not running");
+    }
+
+    let output = Command::new("systemctl")
+        .args(["list-units", "--state=failed", "--no-legend", "--plain"])
+        .output()
+        .context("Failed to run systemctl")?;
+    if !output.status.success() {
+        bail!("systemctl list-units failed");
+    }
+
+    let units = parse_failed_units(&String::from_utf8_lossy(&output.stdout));
+    let value = if units.is_empty() {
+        "0".to_string()
+    } else {
+        format!("{} ({})", units.len(), units.join(", "))
+    };
+    Ok(FieldValue::from(value)
+        .with_part("count", units.len())
+        .with_part("units", units.join(", ")))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_failed_units() -> Result<FieldValue> {
+    anyhow::bail!("Failed units are only available on systemd hosts")
+}
+
+/// Unit names from `systemctl list-units --plain --no-legend`, whose lines are
+/// `UNIT LOAD ACTIVE SUB DESCRIPTION`
+#[cfg(any(target_os = "linux", test))]
+fn parse_failed_units(list: &str) -> Vec<String> {
+    list.lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_name() {
+        assert_eq!(init_name("systemd"), Some("systemd"));
+        assert_eq!(init_name("openrc-init"), Some("OpenRC"));
+        assert_eq!(init_name("s6-svscan"), Some("s6"));
+        assert_eq!(init_name("init"), None);
+        assert_eq!(
+            parse_version("systemd 254 (254.5-1-arch)").as_deref(),
+            Some("254")
+        );
+    }
+
+    #[test]
+    fn test_parse_failed_units() {
+        let list = "nginx.service  loaded failed failed A high performance web server\n\
+            backup.timer   loaded failed failed Nightly backup\n";
+        assert_eq!(
+            parse_failed_units(list),
+            vec!["nginx.service", "backup.timer"]
+        );
+        assert!(parse_failed_units("").is_empty());
+    }
+}
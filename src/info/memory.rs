@@ -0,0 +1,236 @@
+use super::FieldValue;
+use crate::config::{MemoryConfig, UnitsConfig};
+use crate::utils::cgroup;
+use crate::utils::units::format_size;
+#[cfg(target_os = "linux")]
+use anyhow::Context;
+use anyhow::Result;
+#[cfg(target_os = "linux")]
+use std::fs;
+
+/// Memory and swap sizes in bytes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoryStats {
+    pub total: u64,
+    pub available: u64,
+    pub free: u64,
+    /// Buffers plus page cache and reclaimable slab, as `free` reports it
+    pub buffers_cache: Option<u64>,
+    pub shared: Option<u64>,
+    pub swap_total: u64,
+    pub swap_free: u64,
+}
+
+impl MemoryStats {
+    pub fn used(&self) -> u64 {
+        self.total.saturating_sub(self.available)
+    }
+
+    pub fn swap_used(&self) -> u64 {
+        self.swap_total.saturating_sub(self.swap_free)
+    }
+}
+
+/// A zram device, usually used as compressed swap
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Zram {
+    /// Uncompressed size of the data stored
+    pub stored: u64,
+    /// Memory used to hold the compressed data
+    pub compressed: u64,
+    pub disksize: u64,
+}
+
+pub fn get_memory_info(config: &MemoryConfig, units: &UnitsConfig) -> Result<FieldValue> {
+    let stats = read_memory()?;
+    let (mut used, mut total, mut available) = (stats.used(), stats.total, stats.available);
+
+    // A container's limit, when it's below what the host has
+    if config.cgroup_limits {
+        if let Some(limit) = cgroup::memory_limit().filter(|limit| limit.limit < stats.total) {
+            used = limit.usage.min(limit.limit);
+            total = limit.limit;
+            available = total - used;
+        }
+    }
+
+    Ok(usage_value(used, total, units).with_part("available", format_size(available, units)))
+}
+
+pub fn get_swap_info(units: &UnitsConfig) -> Result<FieldValue> {
+    let stats = read_memory()?;
+    if stats.swap_total == 0 {
+        return Ok("Disabled".to_string().into());
+    }
+
+    Ok(usage_value(stats.swap_used(), stats.swap_total, units))
+}
+
+/// Where the memory that isn't "used" went: available, buffers/cache, shared
+/// and zram, when the platform reports them
+pub fn get_memory_details(units: &UnitsConfig) -> Result<FieldValue> {
+    let stats = read_memory()?;
+    let size = |bytes| format_size(bytes, units);
+
+    let mut details = vec![format!("{} available", size(stats.available))];
+    let mut field = FieldValue::default().with_part("available", size(stats.available));
+
+    if let Some(buffers_cache) = stats.buffers_cache {
+        details.push(format!("{} buffers/cache", size(buffers_cache)));
+        field = field.with_part("buffers_cache", size(buffers_cache));
+    }
+    if let Some(shared) = stats.shared {
+        details.push(format!("{} shared", size(shared)));
+        field = field.with_part("shared", size(shared));
+    }
+    if let Some(zram) = read_zram() {
+        details.push(format!(
+            "{} / {} zram",
+            size(zram.stored),
+            size(zram.disksize)
+        ));
+        field = field
+            .with_part("zram", size(zram.stored))
+            .with_part("zram_total", size(zram.disksize))
+            .with_part("zram_compressed", size(zram.compressed));
+    }
+
+    field.value = details.join(", ");
+    Ok(field)
+}
+
+fn usage_value(used: u64, total: u64, units: &UnitsConfig) -> FieldValue {
+    let usage_percent = used as f64 / total as f64 * 100.0;
+    let (used, total) = (format_size(used, units), format_size(total, units));
+
+    FieldValue::from(format!("{} / {} ({:.1}%)", used, total, usage_percent))
+        .with_part("used", used)
+        .with_part("total", total)
+        .with_part("percent", format!("{:.1}", usage_percent))
+}
+
+/// Read memory sizes without scanning processes: `/proc/meminfo` on Linux,
+/// a memory-only sysinfo refresh elsewhere
+pub fn read_memory() -> Result<MemoryStats> {
+    #[cfg(target_os = "linux")]
+    {
+        let meminfo =
+            fs::read_to_string("/proc/meminfo").context("Failed to read /proc/meminfo")?;
+        Ok(parse_meminfo(&meminfo))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let mut sys = sysinfo::System::new();
+        sys.refresh_memory();
+        Ok(MemoryStats {
+            total: sys.total_memory(),
+            available: sys.available_memory(),
+            free: sys.free_memory(),
+            buffers_cache: None,
+            shared: None,
+            swap_total: sys.total_swap(),
+            swap_free: sys.free_swap(),
+        })
+    }
+}
+
+/// Parse `/proc/meminfo`, whose values are in KiB
+pub fn parse_meminfo(meminfo: &str) -> MemoryStats {
+    let value = |key: &str| {
+        meminfo.lines().find_map(|line| {
+            let rest = line.strip_prefix(key)?.strip_prefix(':')?;
+            let kib: u64 = rest.trim().trim_end_matches("kB").trim().parse().ok()?;
+            Some(kib * 1024)
+        })
+    };
+
+    let total = value("MemTotal").unwrap_or(0);
+    let free = value("MemFree").unwrap_or(0);
+    let buffers_cache = match (value("Buffers"), value("Cached")) {
+        (Some(buffers), Some(cached)) => {
+            Some(buffers + cached + value("SReclaimable").unwrap_or(0))
+        }
+        _ => None,
+    };
+
+    MemoryStats {
+        total,
+        // Kernels before 3.14 don't report MemAvailable
+        available: value("MemAvailable").unwrap_or(free + buffers_cache.unwrap_or(0)),
+        free,
+        buffers_cache,
+        shared: value("Shmem"),
+        swap_total: value("SwapTotal").unwrap_or(0),
+        swap_free: value("SwapFree").unwrap_or(0),
+    }
+}
+
+/// Sum of all initialized zram devices
+#[cfg(target_os = "linux")]
+fn read_zram() -> Option<Zram> {
+    let mut total = Zram::default();
+
+    for entry in fs::read_dir("/sys/block").ok()?.flatten() {
+        if !entry.file_name().to_string_lossy().starts_with("zram") {
+            continue;
+        }
+        let path = entry.path();
+        let disksize: u64 = fs::read_to_string(path.join("disksize"))
+            .ok()
+            .and_then(|size| size.trim().parse().ok())
+            .unwrap_or(0);
+        if disksize == 0 {
+            continue;
+        }
+
+        // mm_stat: orig_data_size compr_data_size mem_used_total ...
+        let stat: Vec<u64> = fs::read_to_string(path.join("mm_stat"))
+            .unwrap_or_default()
+            .split_whitespace()
+            .filter_map(|n| n.parse().ok())
+            .collect();
+
+        total.disksize += disksize;
+        total.stored += stat.first().copied().unwrap_or(0);
+        total.compressed += stat.get(2).copied().unwrap_or(0);
+    }
+
+    (total.disksize > 0).then_some(total)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_zram() -> Option<Zram> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_meminfo() {
+        let meminfo = "MemTotal:       16000000 kB\n\
+                       MemFree:         2000000 kB\n\
+                       MemAvailable:    9000000 kB\n\
+                       Buffers:          500000 kB\n\
+                       Cached:          6000000 kB\n\
+                       SwapCached:        10000 kB\n\
+                       SwapTotal:       4000000 kB\n\
+                       SwapFree:        3000000 kB\n\
+                       Shmem:            300000 kB\n\
+                       SReclaimable:     400000 kB\n";
+
+        let stats = parse_meminfo(meminfo);
+        assert_eq!(stats.total, 16_000_000 * 1024);
+        assert_eq!(stats.used(), 7_000_000 * 1024);
+        assert_eq!(stats.buffers_cache, Some(6_900_000 * 1024));
+        assert_eq!(stats.shared, Some(300_000 * 1024));
+        assert_eq!(stats.swap_used(), 1_000_000 * 1024);
+
+        // SwapCached must not be mistaken for Cached
+        let stats = parse_meminfo("MemTotal: 1000 kB\nMemFree: 100 kB\nSwapCached: 50 kB\n");
+        assert_eq!(stats.buffers_cache, None);
+        assert_eq!(stats.available, 100 * 1024);
+    }
+}
@@ -0,0 +1,273 @@
+use super::{disk, hardware, system};
+use crate::config::DiskConfig;
+use sysinfo::{Components, Networks, System};
+
+/// Whether a metric can go up and down or only grows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MetricKind {
+    Gauge,
+    Counter,
+}
+
+impl MetricKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MetricKind::Gauge => "gauge",
+            MetricKind::Counter => "counter",
+        }
+    }
+}
+
+/// A single sample for metrics output
+#[derive(Debug, Clone)]
+pub struct Metric {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub kind: MetricKind,
+    pub labels: Vec<(&'static str, String)>,
+    pub value: f64,
+}
+
+impl Metric {
+    fn new(name: &'static str, help: &'static str, value: impl Into<f64>) -> Self {
+        Self {
+            name,
+            help,
+            kind: MetricKind::Gauge,
+            labels: Vec::new(),
+            value: value.into(),
+        }
+    }
+
+    /// A counter, whose name should end in `_total`
+    fn counter(name: &'static str, help: &'static str, value: impl Into<f64>) -> Self {
+        Self {
+            kind: MetricKind::Counter,
+            ..Self::new(name, help, value)
+        }
+    }
+
+    fn label(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.labels.push((name, value.to_string()));
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MetricGroup {
+    System,
+    Cpu,
+    Memory,
+    Disk,
+    Network,
+    Sensors,
+}
+
+impl MetricGroup {
+    pub const ALL: &'static [MetricGroup] = &[
+        MetricGroup::System,
+        MetricGroup::Cpu,
+        MetricGroup::Memory,
+        MetricGroup::Disk,
+        MetricGroup::Network,
+        MetricGroup::Sensors,
+    ];
+}
+
+/// Collect numeric metrics for the requested groups. `sys` must have CPU and
+/// memory information refreshed, with CPU usage sampled over a window.
+/// Disks are the ones the `disk` field shows.
+pub fn collect_metrics(sys: &System, groups: &[MetricGroup], disk: &DiskConfig) -> Vec<Metric> {
+    let mut metrics = Vec::new();
+
+    for group in groups {
+        match group {
+            MetricGroup::System => system_metrics(sys, &mut metrics),
+            MetricGroup::Cpu => cpu_metrics(sys, &mut metrics),
+            MetricGroup::Memory => memory_metrics(sys, &mut metrics),
+            MetricGroup::Disk => disk_metrics(disk, &mut metrics),
+            MetricGroup::Network => network_metrics(&mut metrics),
+            MetricGroup::Sensors => sensor_metrics(&mut metrics),
+        }
+    }
+
+    metrics
+}
+
+fn system_metrics(sys: &System, metrics: &mut Vec<Metric>) {
+    let os = system::get_os_info()
+        .map(|os| os.value)
+        .unwrap_or_else(|_| "Unknown".to_string());
+    let cpu = sys
+        .cpus()
+        .first()
+        .map(|cpu| cpu.brand().trim().to_string())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    metrics.push(
+        Metric::new("rice_info", "Static system facts as labels", 1)
+            .label("os", os)
+            .label(
+                "kernel",
+                System::kernel_version().unwrap_or_else(|| "Unknown".to_string()),
+            )
+            .label(
+                "hostname",
+                System::host_name().unwrap_or_else(|| "Unknown".to_string()),
+            )
+            .label("cpu", cpu),
+    );
+    metrics.push(Metric::new(
+        "rice_uptime_seconds",
+        "System uptime in seconds",
+        System::uptime() as f64,
+    ));
+    metrics.push(Metric::new(
+        "rice_boot_time_seconds",
+        "System boot time as a Unix timestamp",
+        System::boot_time() as f64,
+    ));
+
+    let battery = hardware::get_battery_info().ok();
+    if let Some(percent) = battery
+        .as_ref()
+        .and_then(|battery| battery.part("percent"))
+        .and_then(|percent| percent.parse::<f64>().ok())
+    {
+        metrics.push(Metric::new(
+            "rice_battery_percent",
+            "Battery charge in percent",
+            percent,
+        ));
+    }
+}
+
+fn cpu_metrics(sys: &System, metrics: &mut Vec<Metric>) {
+    metrics.push(Metric::new(
+        "rice_cpu_count",
+        "Number of logical CPUs",
+        sys.cpus().len() as f64,
+    ));
+
+    for (i, cpu) in sys.cpus().iter().enumerate() {
+        metrics.push(
+            Metric::new(
+                "rice_cpu_frequency_mhz",
+                "Current CPU frequency in MHz",
+                cpu.frequency() as f64,
+            )
+            .label("core", i),
+        );
+        metrics.push(
+            Metric::new(
+                "rice_cpu_usage_percent",
+                "CPU usage in percent over the sampling window",
+                cpu.cpu_usage(),
+            )
+            .label("core", i),
+        );
+    }
+
+    if let Ok((one, five, fifteen)) = system::read_load_average() {
+        for (period, load) in [("1m", one), ("5m", five), ("15m", fifteen)] {
+            metrics.push(
+                Metric::new("rice_load_average", "System load average", load)
+                    .label("period", period),
+            );
+        }
+    }
+}
+
+fn memory_metrics(sys: &System, metrics: &mut Vec<Metric>) {
+    metrics.push(Metric::new(
+        "rice_memory_total_bytes",
+        "Total memory in bytes",
+        sys.total_memory() as f64,
+    ));
+    metrics.push(Metric::new(
+        "rice_memory_used_bytes",
+        "Used memory in bytes",
+        sys.used_memory() as f64,
+    ));
+    metrics.push(Metric::new(
+        "rice_memory_available_bytes",
+        "Available memory in bytes",
+        sys.available_memory() as f64,
+    ));
+    metrics.push(Metric::new(
+        "rice_swap_total_bytes",
+        "Total swap in bytes",
+        sys.total_swap() as f64,
+    ));
+    metrics.push(Metric::new(
+        "rice_swap_used_bytes",
+        "Used swap in bytes",
+        sys.used_swap() as f64,
+    ));
+}
+
+fn disk_metrics(config: &DiskConfig, metrics: &mut Vec<Metric>) {
+    for mount in disk::list_mounts(config) {
+        let labels = [
+            ("mount", mount.mount_point),
+            ("device", mount.device),
+            ("fstype", mount.fs_type),
+        ];
+
+        let mut total = Metric::new(
+            "rice_disk_total_bytes",
+            "Total disk space in bytes",
+            mount.total as f64,
+        );
+        let mut available = Metric::new(
+            "rice_disk_available_bytes",
+            "Available disk space in bytes",
+            mount.available as f64,
+        );
+        total.labels.extend(labels.clone());
+        available.labels.extend(labels);
+
+        metrics.push(total);
+        metrics.push(available);
+    }
+}
+
+fn network_metrics(metrics: &mut Vec<Metric>) {
+    let networks = Networks::new_with_refreshed_list();
+
+    for (name, data) in &networks {
+        metrics.push(
+            Metric::counter(
+                "rice_network_received_bytes_total",
+                "Bytes received on the interface",
+                data.total_received() as f64,
+            )
+            .label("interface", name),
+        );
+        metrics.push(
+            Metric::counter(
+                "rice_network_transmitted_bytes_total",
+                "Bytes transmitted on the interface",
+                data.total_transmitted() as f64,
+            )
+            .label("interface", name),
+        );
+    }
+}
+
+fn sensor_metrics(metrics: &mut Vec<Metric>) {
+    let components = Components::new_with_refreshed_list();
+
+    for component in &components {
+        if let Some(temperature) = component.temperature().filter(|t| t.is_finite()) {
+            metrics.push(
+                Metric::new(
+                    "rice_temperature_celsius",
+                    "Sensor temperature in degrees Celsius",
+                    temperature,
+                )
+                .label("sensor", component.label()),
+            );
+        }
+    }
+}
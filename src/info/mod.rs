@@ -1,37 +1,195 @@
+pub mod cpu;
 pub mod custom;
+pub mod disk;
 pub mod hardware;
+pub mod init;
+pub mod memory;
+pub mod metrics;
+pub mod monitors;
 pub mod network;
+pub mod shell;
 pub mod software;
 pub mod system;
+pub mod terminal;
+pub mod terminal_font;
+pub mod theme;
+pub mod user;
+pub mod virt;
+pub mod wm;
 
+use crate::config::{Config, CpuConfig, DateTimeConfig, DiskConfig, MemoryConfig, UnitsConfig};
 use anyhow::Result;
+use serde::Serialize;
 use std::collections::HashMap;
 
+/// Fields whose values change while rice is running, re-collected by `--watch`
+pub const VOLATILE_FIELDS: &[&str] = &[
+    "uptime",
+    "memory",
+    "memory_details",
+    "swap",
+    "battery",
+    "temperature",
+    "cpu_usage",
+    "load",
+    "datetime",
+];
+
+/// A collected field value along with named parts that templates can
+/// reference individually as `{field.part}`
+#[derive(Debug, Clone, Default)]
+pub struct FieldValue {
+    pub value: String,
+    pub parts: Vec<(&'static str, String)>,
+    /// Related values collected along with this one, stored under their own
+    /// names, e.g. `disk:/home` for `disk`
+    pub children: Vec<(String, FieldValue)>,
+}
+
+impl FieldValue {
+    pub fn with_part(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.parts.push((name, value.to_string()));
+        self
+    }
+
+    pub fn with_child(mut self, name: String, value: FieldValue) -> Self {
+        self.children.push((name, value));
+        self
+    }
+
+    pub fn part(&self, name: &str) -> Option<&str> {
+        self.parts
+            .iter()
+            .find(|(part, _)| *part == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl From<String> for FieldValue {
+    fn from(value: String) -> Self {
+        Self {
+            value,
+            ..Default::default()
+        }
+    }
+}
+
+/// Collected info. Only the top-level fields are the info itself and get
+/// serialized; parts and related values are kept apart for templates and
+/// layouts to look up.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(transparent)]
+pub struct Info {
+    fields: HashMap<String, String>,
+    /// `field.part` values and children, e.g. `memory.used` and `disk:/home`
+    #[serde(skip)]
+    details: HashMap<String, String>,
+    /// Names of each field's children in the order they were collected
+    #[serde(skip)]
+    children: HashMap<String, Vec<String>>,
+}
+
+impl Info {
+    /// A field, part or child value by name
+    pub fn get(&self, name: &str) -> Option<&String> {
+        self.fields.get(name).or_else(|| self.details.get(name))
+    }
+
+    /// Whether a top-level field was collected
+    pub fn contains(&self, field: &str) -> bool {
+        self.fields.contains_key(field)
+    }
+
+    /// Names of the values collected along with a field, e.g. `disk:/` and
+    /// `disk:/home` for `disk`
+    pub fn children(&self, field: &str) -> &[String] {
+        self.children.get(field).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn insert(&mut self, field: &str, value: FieldValue) {
+        self.insert_details(field, &value);
+        self.fields.insert(field.to_string(), value.value);
+    }
+
+    fn insert_details(&mut self, field: &str, value: &FieldValue) {
+        for (part, part_value) in &value.parts {
+            self.details
+                .insert(format!("{}.{}", field, part), part_value.clone());
+        }
+        for (child, child_value) in &value.children {
+            self.insert_details(child, child_value);
+            self.details
+                .insert(child.clone(), child_value.value.clone());
+        }
+        self.children.insert(
+            field.to_string(),
+            value
+                .children
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect(),
+        );
+    }
+
+    /// Replace a field's value, keeping its parts
+    pub fn set(&mut self, field: &str, value: String) {
+        self.fields.insert(field.to_string(), value);
+    }
+
+    pub fn remove(&mut self, field: &str) {
+        self.fields.remove(field);
+    }
+}
+
 pub struct InfoCollector {
     fields: Vec<String>,
     custom_commands: HashMap<String, String>,
+    cpu: CpuConfig,
+    memory: MemoryConfig,
+    disk: DiskConfig,
+    units: UnitsConfig,
+    datetime: DateTimeConfig,
 }
 
 impl InfoCollector {
-    pub fn new(fields: Vec<String>, custom_commands: HashMap<String, String>) -> Self {
+    pub fn new(fields: Vec<String>, config: &Config) -> Self {
         Self {
             fields,
-            custom_commands,
+            custom_commands: config.info.custom_commands.clone(),
+            cpu: config.cpu.clone(),
+            memory: config.memory.clone(),
+            disk: config.disk.clone(),
+            units: config.units.clone(),
+            datetime: config.datetime.clone(),
         }
     }
 
-    pub fn collect_all(&self) -> Result<HashMap<String, String>> {
-        let mut info = HashMap::new();
+    pub fn collect_all(&self) -> Result<Info> {
+        let mut info = Info::default();
 
         for field in &self.fields {
-            if let Ok(value) = self.collect_field(field) {
-                info.insert(field.clone(), value);
-            }
+            self.collect_into(&mut info, field);
         }
 
         Ok(info)
     }
 
+    /// Re-collect the given fields, keeping previously collected values for the rest
+    pub fn refresh(&self, info: &mut Info, fields: &[&str]) {
+        for field in self.fields.iter().filter(|f| fields.contains(&f.as_str())) {
+            // Custom commands may share a built-in name but aren't cheap to re-run
+            if !self.custom_commands.contains_key(field) {
+                self.collect_into(info, field);
+            }
+        }
+    }
+
+    fn collect_into(&self, info: &mut Info, field: &str) {
+        if let Ok(value) = self.collect_field(field) {
+            info.insert(field, value);
+        }
+    }
+
     fn get_color_blocks(&self) -> Result<String> {
         use colored::*;
 
@@ -63,31 +221,91 @@ impl InfoCollector {
         Ok(format!("{}\n{}", normal_blocks, bright_blocks))
     }
 
-    fn collect_field(&self, field: &str) -> Result<String> {
+    fn collect_field(&self, field: &str) -> Result<FieldValue> {
         // Check if this is a custom command first
         if let Some(command) = self.custom_commands.get(field) {
-            return custom::execute_custom_command(command);
+            return custom::execute_custom_command(command).map(Into::into);
         }
 
         // Fall back to built-in fields
         match field {
             "os" => system::get_os_info(),
-            "hostname" => system::get_hostname(),
+            "hostname" => system::get_hostname().map(Into::into),
             "userhost" => system::get_userhost(),
-            "kernel" => system::get_kernel_version(),
+            "kernel" => system::get_kernel_version().map(Into::into),
             "uptime" => system::get_uptime(),
-            "cpu" => hardware::get_cpu_info(),
-            "memory" => hardware::get_memory_info(),
-            "disk" => hardware::get_disk_info(),
-            "shell" => software::get_shell_info(),
-            "terminal" => software::get_terminal_info(),
-            "terminal_font" => software::get_terminal_font(),
-            "packages" => software::get_package_count(),
-            "resolution" => software::get_resolution(),
-            "de" => software::get_desktop_environment(),
-            "wm" => software::get_window_manager(),
-            "colors" => self.get_color_blocks(),
-            _ => Ok("Unknown".to_string()),
+            "cpu" => cpu::get_cpu_info(&self.cpu),
+            "cpu_cache" => cpu::get_cpu_cache(),
+            "cpu_usage" => hardware::get_cpu_usage(&self.cpu),
+            "load" => system::get_load_average(),
+            "memory" => memory::get_memory_info(&self.memory, &self.units),
+            "memory_details" => memory::get_memory_details(&self.units),
+            "swap" => memory::get_swap_info(&self.units),
+            "disk" => disk::get_disk_info(&self.disk, &self.units),
+            "battery" => hardware::get_battery_info(),
+            "temperature" => hardware::get_temperature(),
+            "shell" => shell::get_shell_info(),
+            "terminal" => terminal::get_terminal_info(),
+            "terminal_font" => terminal_font::get_terminal_font().map(Into::into),
+            "packages" => software::get_package_count().map(Into::into),
+            "resolution" => monitors::get_resolution(),
+            "de" => software::get_desktop_environment().map(Into::into),
+            "wm" => wm::get_window_manager(),
+            "session" => wm::get_session(),
+            "virtualization" => virt::get_virtualization(),
+            "init" => init::get_init(),
+            "failed_units" => init::get_failed_units(),
+            "locale" => user::get_locale(),
+            "timezone" => user::get_timezone(),
+            "users" => user::get_users(),
+            "boot_time" => user::get_boot_time(&self.datetime),
+            "datetime" => user::get_datetime(&self.datetime),
+            "theme" => theme::get_theme(),
+            "icons" => theme::get_icons(),
+            "cursor" => theme::get_cursor(),
+            "font" => theme::get_font(),
+            "colors" => self.get_color_blocks().map(Into::into),
+            _ => match field.strip_prefix("disk:") {
+                Some(mount_point) => disk::get_mount_info(mount_point, &self.disk, &self.units),
+                None => Ok("Unknown".to_string().into()),
+            },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_info_serializes_only_fields() {
+        let mut info = Info::default();
+        info.insert(
+            "memory",
+            FieldValue::from("4 GiB / 16 GiB".to_string()).with_part("used", "4 GiB"),
+        );
+        info.insert(
+            "disk",
+            FieldValue::from("20 GiB / 100 GiB".to_string()).with_child(
+                "disk:/".to_string(),
+                FieldValue::from("20 GiB / 100 GiB".to_string()).with_part("used", "20 GiB"),
+            ),
+        );
+
+        let json = serde_json::to_value(&info).unwrap();
+        let mut keys: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort();
+        assert_eq!(keys, vec!["disk", "memory"]);
+
+        assert_eq!(info.get("memory.used").map(String::as_str), Some("4 GiB"));
+        assert_eq!(info.get("disk:/.used").map(String::as_str), Some("20 GiB"));
+        assert!(info.contains("disk") && !info.contains("disk:/"));
+        assert_eq!(info.children("disk"), ["disk:/".to_string()]);
+        assert!(info.children("memory").is_empty());
+    }
+}
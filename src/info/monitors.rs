@@ -0,0 +1,272 @@
+use super::FieldValue;
+use anyhow::Result;
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use std::process::Command;
+
+/// A connected display and its mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monitor {
+    pub connector: String,
+    pub width: u32,
+    pub height: u32,
+    pub refresh: Option<f64>,
+}
+
+impl Monitor {
+    /// "2560x1440 @ 144 Hz", or just the resolution when the refresh rate is unknown
+    fn describe(&self) -> String {
+        match self.refresh {
+            Some(refresh) => format!(
+                "{}x{} @ {} Hz",
+                self.width,
+                self.height,
+                format_refresh(refresh)
+            ),
+            None => format!("{}x{}", self.width, self.height),
+        }
+    }
+}
+
+/// Whole numbers for rates like 60.00 and 143.98, two decimals for 59.94
+fn format_refresh(refresh: f64) -> String {
+    if (refresh - refresh.round()).abs() < 0.05 {
+        format!("{:.0}", refresh)
+    } else {
+        format!("{:.2}", refresh)
+    }
+}
+
+pub fn get_resolution() -> Result<FieldValue> {
+    let monitors = list_monitors();
+    let Some(first) = monitors.first() else {
+        return Ok("Unknown".to_string().into());
+    };
+
+    let value = monitors
+        .iter()
+        .map(Monitor::describe)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut field = FieldValue::from(value)
+        .with_part("count", monitors.len())
+        .with_part("width", first.width)
+        .with_part("height", first.height);
+    if let Some(refresh) = first.refresh {
+        field = field.with_part("refresh", format_refresh(refresh));
+    }
+    Ok(field)
+}
+
+/// Active monitors and their preferred modes from the kernel's DRM
+/// connectors, which works under X11, Wayland and on the console, falling
+/// back to xrandr
+#[cfg(target_os = "linux")]
+pub fn list_monitors() -> Vec<Monitor> {
+    let monitors = read_drm();
+    if !monitors.is_empty() {
+        return monitors;
+    }
+
+    Command::new("xrandr")
+        .arg("--current")
+        .output()
+        .map(|output| parse_xrandr(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "macos")]
+pub fn list_monitors() -> Vec<Monitor> {
+    let Ok(output) = Command::new("system_profiler")
+        .arg("SPDisplaysDataType")
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    // "Resolution: 2560 x 1440 (QHD/WQHD - Wide Quad High Definition)"
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split("Resolution:").nth(1))
+        .enumerate()
+        .filter_map(|(i, resolution)| {
+            let mut numbers = resolution
+                .split(|c: char| !c.is_ascii_digit())
+                .filter(|n| !n.is_empty());
+            Some(Monitor {
+                connector: format!("Display {}", i + 1),
+                width: numbers.next()?.parse().ok()?,
+                height: numbers.next()?.parse().ok()?,
+                refresh: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn list_monitors() -> Vec<Monitor> {
+    Vec::new()
+}
+
+/// `/sys/class/drm/card0-HDMI-A-1` and friends that are connected and enabled,
+/// i.e. showing a picture. The first mode listed is the monitor's preferred
+/// one, which is what's reported rather than the mode currently set.
+#[cfg(target_os = "linux")]
+fn read_drm() -> Vec<Monitor> {
+    let Ok(entries) = fs::read_dir("/sys/class/drm") else {
+        return Vec::new();
+    };
+
+    let mut connectors: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join("status").exists())
+        .collect();
+    connectors.sort();
+
+    connectors
+        .iter()
+        .filter_map(|path| {
+            let status = fs::read_to_string(path.join("status")).ok()?;
+            let enabled = fs::read_to_string(path.join("enabled")).ok()?;
+            if status.trim() != "connected" || enabled.trim() != "enabled" {
+                return None;
+            }
+            let modes = fs::read_to_string(path.join("modes")).ok()?;
+            let (width, height) = parse_mode(modes.lines().next()?)?;
+
+            let edid = fs::read(path.join("edid")).unwrap_or_default();
+            let refresh = edid_preferred_timing(&edid)
+                .filter(|timing| (timing.0, timing.1) == (width, height))
+                .map(|timing| timing.2);
+
+            // "card0-HDMI-A-1" -> "HDMI-A-1"
+            let name = path.file_name()?.to_string_lossy().to_string();
+            let connector = name.split_once('-').map_or(name.as_str(), |(_, c)| c);
+
+            Some(Monitor {
+                connector: connector.to_string(),
+                width,
+                height,
+                refresh,
+            })
+        })
+        .collect()
+}
+
+/// "2560x1440", possibly with a suffix such as "i" for interlaced modes
+fn parse_mode(mode: &str) -> Option<(u32, u32)> {
+    let (width, height) = mode.trim().split_once('x')?;
+    let height: String = height.chars().take_while(char::is_ascii_digit).collect();
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
+
+/// Width, height and refresh rate of the first detailed timing descriptor in
+/// an EDID block, which holds the monitor's preferred mode
+fn edid_preferred_timing(edid: &[u8]) -> Option<(u32, u32, f64)> {
+    const HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+    if edid.get(..8)? != HEADER {
+        return None;
+    }
+
+    let timing = edid.get(54..72)?;
+    let pixel_clock = u16::from_le_bytes([timing[0], timing[1]]) as f64 * 10_000.0;
+    if pixel_clock == 0.0 {
+        return None;
+    }
+
+    let h_active = timing[2] as u32 | ((timing[4] as u32 >> 4) << 8);
+    let h_blank = timing[3] as u32 | ((timing[4] as u32 & 0x0f) << 8);
+    let v_active = timing[5] as u32 | ((timing[7] as u32 >> 4) << 8);
+    let v_blank = timing[6] as u32 | ((timing[7] as u32 & 0x0f) << 8);
+
+    let total = ((h_active + h_blank) * (v_active + v_blank)) as f64;
+    if total == 0.0 {
+        return None;
+    }
+    Some((h_active, v_active, pixel_clock / total))
+}
+
+/// Connected outputs from `xrandr --current`: the geometry on the output
+/// line and the rate marked `*` among its modes
+#[cfg(any(target_os = "linux", test))]
+fn parse_xrandr(output: &str) -> Vec<Monitor> {
+    let mut monitors: Vec<Monitor> = Vec::new();
+    let mut in_connected = false;
+
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            let mut words = line.split_whitespace();
+            let connector = words.next().unwrap_or_default();
+            in_connected = words.next() == Some("connected");
+            if !in_connected {
+                continue;
+            }
+
+            // "HDMI-1 connected primary 2560x1440+0+0 (normal ...) 597mm x 336mm"
+            let geometry = line
+                .split_whitespace()
+                .find(|word| word.contains('x') && word.contains('+'));
+            if let Some((width, height)) = geometry.and_then(|g| parse_mode(g.split('+').next()?)) {
+                monitors.push(Monitor {
+                    connector: connector.to_string(),
+                    width,
+                    height,
+                    refresh: None,
+                });
+            } else {
+                // Connected but turned off
+                in_connected = false;
+            }
+        } else if in_connected {
+            // "   2560x1440     59.95*+ 143.97  "
+            let current = line
+                .split_whitespace()
+                .skip(1)
+                .find(|rate| rate.contains('*'));
+            if let (Some(rate), Some(monitor)) = (current, monitors.last_mut()) {
+                monitor.refresh = rate.trim_end_matches(['*', '+']).parse().ok();
+                in_connected = false;
+            }
+        }
+    }
+
+    monitors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edid_preferred_timing() {
+        let mut edid = vec![0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+        edid.resize(128, 0);
+        // 1920x1080 at 148.5 MHz with 280/45 blanking: 60 Hz
+        edid[54..62].copy_from_slice(&[0x02, 0x3a, 0x80, 0x18, 0x71, 0x38, 0x2d, 0x40]);
+
+        let (width, height, refresh) = edid_preferred_timing(&edid).unwrap();
+        assert_eq!((width, height), (1920, 1080));
+        assert_eq!(format_refresh(refresh), "60");
+        assert_eq!(edid_preferred_timing(&edid[..40]), None);
+    }
+
+    #[test]
+    fn test_parse_xrandr() {
+        let output = "Screen 0: minimum 8 x 8, current 4480 x 1440, maximum 32767 x 32767\n\
+            DP-1 connected primary 2560x1440+0+0 (normal left inverted right x axis y axis) 597mm x 336mm\n   \
+               2560x1440     59.95 + 143.97*\n   \
+               1920x1080     60.00\n\
+            HDMI-1 connected 1920x1080+2560+0 (normal left inverted right x axis y axis) 527mm x 296mm\n   \
+               1920x1080     60.00*+  50.00\n\
+            HDMI-2 disconnected (normal left inverted right x axis y axis)\n\
+            DP-2 connected (normal left inverted right x axis y axis)\n   \
+               1280x720      60.00 +\n";
+
+        let described: Vec<String> = parse_xrandr(output).iter().map(Monitor::describe).collect();
+        assert_eq!(described, vec!["2560x1440 @ 144 Hz", "1920x1080 @ 60 Hz"]);
+        assert_eq!(format_refresh(59.94), "59.94");
+        assert_eq!(parse_mode("1920x1080i"), Some((1920, 1080)));
+    }
+}
@@ -0,0 +1,200 @@
+use super::terminal::parse_version;
+use super::FieldValue;
+use crate::utils::process;
+use anyhow::Result;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Shells rice recognizes when walking up the process tree
+const SHELLS: &[&str] = &[
+    "bash", "zsh", "fish", "nu", "elvish", "xonsh", "ksh", "ksh93", "mksh", "oksh", "dash", "sh",
+    "ash", "tcsh", "csh", "pwsh", "ion", "osh", "ysh", "yash",
+];
+
+/// Processes between a shell and rice that aren't the shell itself
+const WRAPPERS: &[&str] = &[
+    "sudo", "doas", "su", "env", "nohup", "time", "strace", "cargo", "rice",
+];
+
+/// A detected shell and how to run it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shell {
+    pub name: String,
+    pub path: Option<PathBuf>,
+}
+
+pub fn get_shell_info() -> Result<FieldValue> {
+    let Some(shell) = detect_shell() else {
+        return Ok("Unknown".to_string().into());
+    };
+
+    let version = shell_version(&shell);
+    let value = match &version {
+        Some(version) => format!("{} {}", shell.name, version),
+        None => shell.name.clone(),
+    };
+
+    let mut field = FieldValue::from(value).with_part("name", &shell.name);
+    if let Some(version) = version {
+        field = field.with_part("version", version);
+    }
+    if let Some(path) = &shell.path {
+        field = field.with_part("path", path.display());
+    }
+    Ok(field)
+}
+
+/// The shell rice was started from, or the login shell from `$SHELL` when
+/// rice wasn't started from a shell
+pub fn detect_shell() -> Option<Shell> {
+    for process in process::ancestors(std::process::id()) {
+        let program = process.program();
+        if SHELLS.contains(&program.as_str()) {
+            return Some(Shell {
+                name: program,
+                path: process.exe,
+            });
+        }
+        if !WRAPPERS.contains(&program.as_str()) {
+            break;
+        }
+    }
+
+    let path = PathBuf::from(env::var_os("SHELL")?);
+    Some(Shell {
+        name: path.file_name()?.to_string_lossy().to_string(),
+        path: Some(path),
+    })
+}
+
+fn shell_version(shell: &Shell) -> Option<String> {
+    // Some shells export their version, which saves running them
+    let variable = match shell.name.as_str() {
+        "bash" => Some("BASH_VERSION"),
+        "zsh" => Some("ZSH_VERSION"),
+        "fish" => Some("FISH_VERSION"),
+        "nu" => Some("NU_VERSION"),
+        "xonsh" => Some("XONSH_VERSION"),
+        "ksh" | "ksh93" | "mksh" => Some("KSH_VERSION"),
+        _ => None,
+    };
+    if let Some(version) = variable.and_then(|variable| env::var(variable).ok()) {
+        if let Some(version) = parse_shell_version(&shell.name, &version) {
+            return Some(version);
+        }
+    }
+
+    let command = shell
+        .path
+        .as_deref()
+        .unwrap_or_else(|| Path::new(&shell.name));
+    let output = match shell.name.as_str() {
+        // dash has no way of printing its version
+        "dash" => return package_version("dash"),
+        "elvish" => run(command, &["-version"]),
+        // mksh has no --version, but both kshs set $KSH_VERSION
+        "ksh" | "ksh93" | "mksh" | "oksh" => run(command, &["-c", "echo $KSH_VERSION"]),
+        "sh" | "ash" | "csh" | "osh" | "yash" | "ion" => return None,
+        _ => run(command, &["--version"]),
+    }?;
+
+    parse_shell_version(&shell.name, &output)
+}
+
+fn run(command: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new(command).args(args).output().ok()?;
+    // ksh93 prints its version to stderr
+    Some(format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    ))
+}
+
+/// Version of a package from the system package manager, for shells that
+/// can't report their own
+fn package_version(package: &str) -> Option<String> {
+    let queries: &[(&str, &[&str])] = &[
+        ("dpkg-query", &["-W", "-f=${Version}", package]),
+        ("rpm", &["-q", "--qf", "%{VERSION}", package]),
+        ("pacman", &["-Q", package]),
+        ("apk", &["info", "-e", "-v", package]),
+    ];
+
+    queries.iter().find_map(|(command, args)| {
+        let output = Command::new(command).args(*args).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let output = String::from_utf8_lossy(&output.stdout);
+        // "dash 0.5.12-1", "dash-0.5.12-r3" or "0.5.12-2"; drop the package revision
+        let version = output.split_whitespace().last()?;
+        let version = version
+            .strip_prefix(&format!("{}-", package))
+            .unwrap_or(version);
+        Some(version.split('-').next().unwrap_or(version).to_string())
+    })
+}
+
+/// The version number from a shell's version output or variable, e.g.
+/// "5.2.15" from "GNU bash, version 5.2.15(1)-release (x86_64-pc-linux-gnu)"
+fn parse_shell_version(shell: &str, output: &str) -> Option<String> {
+    let output = output.trim();
+    match shell {
+        "bash" => {
+            let version = output
+                .split_once("version ")
+                .map_or(output, |(_, version)| version);
+            let end = version
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(version.len());
+            Some(version[..end].to_string()).filter(|version| !version.is_empty())
+        }
+        // "xonsh/0.14.0"
+        "xonsh" => parse_version(output.trim_start_matches("xonsh/")),
+        // mksh: "@(#)MIRBSD KSH R59 2020/10/31", ksh93: "Version AJM 93u+m/1.0.4 2022-10-22"
+        "ksh" | "ksh93" | "mksh" | "oksh" => {
+            let mut words = output.split_whitespace();
+            if words.any(|word| word == "KSH") {
+                return words.next().map(str::to_string);
+            }
+            parse_version(output)
+        }
+        // zsh 5.9, fish, version 3.6.1, 0.88.1, PowerShell 7.4.0, tcsh 6.24.10 ...
+        _ => parse_version(output),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shell_version() {
+        let version = |shell, output| parse_shell_version(shell, output).unwrap_or_default();
+        assert_eq!(
+            version(
+                "bash",
+                "GNU bash, version 5.2.15(1)-release (x86_64-pc-linux-gnu)\nCopyright (C) 2022"
+            ),
+            "5.2.15"
+        );
+        assert_eq!(version("bash", "5.2.15(1)-release"), "5.2.15");
+        assert_eq!(version("zsh", "zsh 5.9 (x86_64-pc-linux-gnu)"), "5.9");
+        assert_eq!(version("fish", "fish, version 3.6.1"), "3.6.1");
+        assert_eq!(version("nu", "0.88.1\n"), "0.88.1");
+        assert_eq!(version("elvish", "0.19.2"), "0.19.2");
+        assert_eq!(version("xonsh", "xonsh/0.14.0"), "0.14.0");
+        assert_eq!(version("mksh", "@(#)MIRBSD KSH R59 2020/10/31"), "R59");
+        assert_eq!(
+            version("ksh", "Version AJM 93u+m/1.0.4 2022-10-22"),
+            "93u+m/1.0.4"
+        );
+        assert_eq!(
+            version("tcsh", "tcsh 6.24.10 (Astron) 2023-04-14"),
+            "6.24.10"
+        );
+        assert_eq!(version("pwsh", "PowerShell 7.4.0"), "7.4.0");
+    }
+}
@@ -1,10 +1,10 @@
-use anyhow::Result;
-use std::env;
+use super::FieldValue;
+use anyhow::{bail, Result};
 #[cfg(target_os = "macos")]
 use std::process::Command;
 use sysinfo::System;
 
-pub fn get_os_info() -> Result<String> {
+pub fn get_os_info() -> Result<FieldValue> {
     let name = System::name().unwrap_or_else(|| "Unknown".to_string());
     let version = System::os_version().unwrap_or_else(|| "Unknown".to_string());
 
@@ -14,7 +14,9 @@ pub fn get_os_info() -> Result<String> {
         _ => &name,
     };
 
-    Ok(format!("{} {}", display_name, version))
+    Ok(FieldValue::from(format!("{} {}", display_name, version))
+        .with_part("name", display_name)
+        .with_part("version", version))
 }
 
 pub fn get_hostname() -> Result<String> {
@@ -37,27 +39,68 @@ pub fn get_kernel_version() -> Result<String> {
     Ok(System::kernel_version().unwrap_or_else(|| "Unknown".to_string()))
 }
 
-pub fn get_uptime() -> Result<String> {
+pub fn get_uptime() -> Result<FieldValue> {
     let uptime_seconds = System::uptime();
     let days = uptime_seconds / (24 * 3600);
     let hours = (uptime_seconds % (24 * 3600)) / 3600;
     let minutes = (uptime_seconds % 3600) / 60;
 
-    if days > 0 {
-        Ok(format!("{}d {}h {}m", days, hours, minutes))
+    let value = if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
     } else if hours > 0 {
-        Ok(format!("{}h {}m", hours, minutes))
+        format!("{}h {}m", hours, minutes)
     } else {
-        Ok(format!("{}m", minutes))
-    }
-}
+        format!("{}m", minutes)
+    };
 
-pub fn get_userhost() -> Result<String> {
-    let username = env::var("USER")
-        .or_else(|_| env::var("USERNAME"))
-        .unwrap_or_else(|_| "unknown".to_string());
+    Ok(FieldValue::from(value)
+        .with_part("days", days)
+        .with_part("hours", hours)
+        .with_part("minutes", minutes)
+        .with_part("seconds", uptime_seconds))
+}
 
+pub fn get_userhost() -> Result<FieldValue> {
+    let username = super::user::username();
     let hostname = System::host_name().unwrap_or_else(|| "unknown".to_string());
 
-    Ok(format!("{}@{}", username, hostname))
+    Ok(FieldValue::from(format!("{}@{}", username, hostname))
+        .with_part("user", username)
+        .with_part("host", hostname))
+}
+
+pub fn get_load_average() -> Result<FieldValue> {
+    let (one, five, fifteen) = read_load_average()?;
+
+    Ok(
+        FieldValue::from(format!("{:.2} {:.2} {:.2}", one, five, fifteen))
+            .with_part("one", one)
+            .with_part("five", five)
+            .with_part("fifteen", fifteen),
+    )
+}
+
+/// 1, 5 and 15 minute load averages
+pub fn read_load_average() -> Result<(f64, f64, f64)> {
+    #[cfg(target_os = "linux")]
+    {
+        let content = std::fs::read_to_string("/proc/loadavg")?;
+        let mut loads = content
+            .split_whitespace()
+            .map(|load| load.parse::<f64>().ok());
+
+        match (loads.next(), loads.next(), loads.next()) {
+            (Some(Some(one)), Some(Some(five)), Some(Some(fifteen))) => Ok((one, five, fifteen)),
+            _ => bail!("Unexpected /proc/loadavg format"),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        if cfg!(windows) {
+            bail!("Load average not available on Windows");
+        }
+        let load = System::load_average();
+        Ok((load.one, load.five, load.fifteen))
+    }
 }
@@ -0,0 +1,234 @@
+use super::FieldValue;
+use crate::utils::process::{self, Process};
+use anyhow::Result;
+use std::env;
+use std::process::Command;
+
+/// Binary and flag that print a terminal's version
+type VersionCommand = (&'static str, &'static str);
+
+/// A terminal emulator binary, its display name and version command
+type KnownTerminal = (&'static str, &'static str, Option<VersionCommand>);
+
+/// Terminal emulators recognized in the process tree. Only terminals known
+/// to print a version and exit are run.
+const KNOWN_TERMINALS: &[KnownTerminal] = &[
+    (
+        "gnome-terminal-server",
+        "GNOME Terminal",
+        Some(("gnome-terminal", "--version")),
+    ),
+    ("kgx", "GNOME Console", Some(("kgx", "--version"))),
+    ("ptyxis", "Ptyxis", Some(("ptyxis", "--version"))),
+    ("ptyxis-agent", "Ptyxis", Some(("ptyxis", "--version"))),
+    ("konsole", "Konsole", Some(("konsole", "--version"))),
+    ("yakuake", "Yakuake", None),
+    (
+        "xfce4-terminal",
+        "Xfce Terminal",
+        Some(("xfce4-terminal", "--version")),
+    ),
+    (
+        "mate-terminal",
+        "MATE Terminal",
+        Some(("mate-terminal", "--version")),
+    ),
+    ("lxterminal", "LXTerminal", None),
+    ("qterminal", "QTerminal", Some(("qterminal", "--version"))),
+    ("kitty", "kitty", Some(("kitty", "--version"))),
+    ("alacritty", "Alacritty", Some(("alacritty", "--version"))),
+    ("wezterm-gui", "WezTerm", Some(("wezterm", "--version"))),
+    ("foot", "foot", Some(("foot", "--version"))),
+    ("footclient", "foot", Some(("foot", "--version"))),
+    ("ghostty", "Ghostty", Some(("ghostty", "--version"))),
+    (
+        "terminator",
+        "Terminator",
+        Some(("terminator", "--version")),
+    ),
+    ("tilix", "Tilix", Some(("tilix", "--version"))),
+    ("guake", "Guake", None),
+    ("tilda", "Tilda", None),
+    ("terminology", "Terminology", None),
+    ("sakura", "Sakura", None),
+    ("xterm", "xterm", Some(("xterm", "-version"))),
+    ("urxvt", "urxvt", None),
+    ("urxvtd", "urxvt", None),
+    ("st", "st", Some(("st", "-v"))),
+    ("contour", "Contour", None),
+    ("rio", "Rio", None),
+    ("code", "VS Code", None),
+    ("iTerm2", "iTerm2", None),
+    ("Terminal", "Terminal", None),
+    ("Hyper", "Hyper", None),
+    ("tabby", "Tabby", None),
+];
+
+/// Processes that mean no local terminal emulator will be found further up
+const STOP_PROCESSES: &[&str] = &[
+    "sshd",
+    "mosh-server",
+    "systemd",
+    "init",
+    "launchd",
+    "containerd-shim",
+];
+
+/// A detected terminal emulator
+#[derive(Debug, Clone, PartialEq)]
+pub struct Terminal {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+pub fn get_terminal_info() -> Result<FieldValue> {
+    let terminal = detect_terminal();
+
+    let value = match &terminal.version {
+        Some(version) => format!("{} {}", terminal.name, version),
+        None => terminal.name.clone(),
+    };
+    let mut field = FieldValue::from(value).with_part("name", &terminal.name);
+    if let Some(version) = &terminal.version {
+        field = field.with_part("version", version);
+    }
+    Ok(field)
+}
+
+/// Find the terminal emulator by walking up the process tree, falling back
+/// to what the environment says
+pub fn detect_terminal() -> Terminal {
+    match find_terminal_process() {
+        Some((process, (bin, name, version_command))) => Terminal {
+            name: name.to_string(),
+            version: version_command.and_then(|(command, flag)| {
+                // Run the exact binary found when it's the one that prints the version
+                let command = match &process.exe {
+                    Some(exe) if command == *bin => exe.to_string_lossy().to_string(),
+                    _ => command.to_string(),
+                };
+                read_version(&command, flag)
+            }),
+        },
+        None => terminal_from_env(),
+    }
+}
+
+/// The terminal emulator's name as `detect_terminal` finds it, without
+/// running the terminal for its version
+pub fn detect_terminal_name() -> String {
+    match find_terminal_process() {
+        Some((_, (_, name, _))) => name.to_string(),
+        None => terminal_from_env().name,
+    }
+}
+
+fn terminal_from_env() -> Terminal {
+    // Terminals that set TERM_PROGRAM (mostly on macOS) say who they are,
+    // though the variable is inherited by anything started from them
+    if let Ok(term) = env::var("TERM_PROGRAM") {
+        if term != "tmux" && term != "screen" {
+            let name = match term.as_str() {
+                "iTerm.app" => "iTerm2",
+                "Apple_Terminal" => "Terminal",
+                "vscode" => "VS Code",
+                "WezTerm" => "WezTerm",
+                "ghostty" => "Ghostty",
+                _ => &term,
+            };
+            return Terminal {
+                name: name.to_string(),
+                version: env::var("TERM_PROGRAM_VERSION").ok(),
+            };
+        }
+    }
+
+    let name = env::var("TERMINAL_EMULATOR")
+        .or_else(|_| env::var("TERM"))
+        .unwrap_or_else(|_| "Unknown".to_string());
+    Terminal {
+        name,
+        version: None,
+    }
+}
+
+/// The closest ancestor of rice that's a known terminal emulator. Shells,
+/// multiplexers and anything else in between are walked past, and inside
+/// tmux the walk continues from the attached client.
+fn find_terminal_process() -> Option<(Process, &'static KnownTerminal)> {
+    let mut pid = std::process::id();
+    let mut left_tmux = false;
+
+    'walk: loop {
+        for process in process::ancestors(pid) {
+            let program = process.program();
+
+            // The tmux server is detached from any terminal, but its client isn't
+            if program.starts_with("tmux") && !left_tmux {
+                if let Some(client) = tmux_client_pid() {
+                    pid = client;
+                    left_tmux = true;
+                    continue 'walk;
+                }
+            }
+            if STOP_PROCESSES.contains(&program.as_str()) {
+                return None;
+            }
+            if let Some(known) = KNOWN_TERMINALS.iter().find(|(bin, _, _)| *bin == program) {
+                return Some((process, known));
+            }
+        }
+        return None;
+    }
+}
+
+fn tmux_client_pid() -> Option<u32> {
+    env::var_os("TMUX")?;
+    let output = Command::new("tmux")
+        .args(["display-message", "-p", "#{client_pid}"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+fn read_version(command: &str, flag: &str) -> Option<String> {
+    let output = Command::new(command).arg(flag).output().ok()?;
+    // Some terminals, e.g. st, print their version to stderr
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    parse_version(&text)
+}
+
+/// The first word that starts with a digit, e.g. "3.48.1" from
+/// "# GNOME Terminal 3.48.1 using VTE 0.72.2" or "379" from "XTerm(379)"
+pub fn parse_version(output: &str) -> Option<String> {
+    output
+        .split(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | ':' | ','))
+        .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|word| word.trim_end_matches('.').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        let version = |output| parse_version(output).unwrap_or_default();
+        assert_eq!(
+            version("# GNOME Terminal 3.48.1 using VTE 0.72.2 +BIDI"),
+            "3.48.1"
+        );
+        assert_eq!(version("kitty 0.26.5 created by Kovid Goyal"), "0.26.5");
+        assert_eq!(version("foot version: 1.16.2 -pgo +ime"), "1.16.2");
+        assert_eq!(version("XTerm(379)"), "379");
+        assert_eq!(
+            version("wezterm 20230712-072601-f4abf8fd"),
+            "20230712-072601-f4abf8fd"
+        );
+        assert_eq!(parse_version("Usage: st [-aiv]"), None);
+    }
+}
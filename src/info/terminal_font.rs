@@ -0,0 +1,417 @@
+use super::terminal;
+use crate::utils::config_files::{command_output, config_home, font_with_size, ini_value, unquote};
+use anyhow::Result;
+use std::fs;
+use std::path::PathBuf;
+
+pub fn get_terminal_font() -> Result<String> {
+    // Only the name matters here, so the terminal isn't run for its version
+    let font = match terminal::detect_terminal_name().as_str() {
+        "Alacritty" => alacritty_font(),
+        "kitty" => kitty_font(),
+        "foot" => foot_font(),
+        "WezTerm" => wezterm_font(),
+        "xterm" => xresources_font("xterm"),
+        "urxvt" => xresources_font("urxvt"),
+        "Konsole" => konsole_font(),
+        "GNOME Terminal" => gnome_terminal_font(),
+        "Terminal" => apple_terminal_font(),
+        "iTerm2" => iterm2_font(),
+        _ => None,
+    };
+
+    Ok(font.unwrap_or_else(|| "Unknown".to_string()))
+}
+
+/// The first of the given files that can be read
+fn read_first(paths: &[PathBuf]) -> Option<(PathBuf, String)> {
+    paths
+        .iter()
+        .find_map(|path| Some((path.clone(), fs::read_to_string(path).ok()?)))
+}
+
+fn alacritty_font() -> Option<String> {
+    let dir = config_home()?.join("alacritty");
+    let (path, contents) = read_first(&[
+        dir.join("alacritty.toml"),
+        dir.join("alacritty.yml"),
+        dir.join("alacritty.yaml"),
+    ])?;
+
+    if path
+        .extension()
+        .is_some_and(|extension| extension == "toml")
+    {
+        parse_alacritty_toml(&contents)
+    } else {
+        parse_alacritty_yaml(&contents)
+    }
+}
+
+fn parse_alacritty_toml(contents: &str) -> Option<String> {
+    let config: toml::Table = contents.parse().ok()?;
+    let font = config.get("font")?;
+    let family = font.get("normal")?.get("family")?.as_str()?;
+    let size = font.get("size").map(|size| match size {
+        toml::Value::Float(size) => size.to_string(),
+        toml::Value::Integer(size) => size.to_string(),
+        other => other.to_string(),
+    });
+
+    font_with_size(family, size.as_deref())
+}
+
+/// Older alacritty versions used YAML: `family` under `font.normal`, and `font.size`
+fn parse_alacritty_yaml(contents: &str) -> Option<String> {
+    let mut in_font = false;
+    let mut child_indent = None;
+    let mut section = "";
+    let mut family = None;
+    let mut size = None;
+
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+
+        if indent == 0 {
+            in_font = trimmed == "font:";
+            child_indent = None;
+            continue;
+        }
+        if !in_font {
+            continue;
+        }
+
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let child_indent = *child_indent.get_or_insert(indent);
+        if indent == child_indent {
+            section = key;
+            if key == "size" {
+                size = Some(value.trim().to_string());
+            }
+        } else if section == "normal" && key == "family" {
+            family = Some(unquote(value).to_string());
+        }
+    }
+
+    font_with_size(&family?, size.as_deref())
+}
+
+fn kitty_font() -> Option<String> {
+    let contents = fs::read_to_string(config_home()?.join("kitty/kitty.conf")).ok()?;
+    parse_kitty(&contents)
+}
+
+fn parse_kitty(contents: &str) -> Option<String> {
+    let mut family = None;
+    let mut size = None;
+
+    // Later settings win
+    for line in contents.lines() {
+        let line = line.trim();
+        let Some((key, value)) = line.split_once(char::is_whitespace) else {
+            continue;
+        };
+        match key {
+            "font_family" => family = Some(value.trim().to_string()),
+            "font_size" => size = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+
+    let family = family?;
+    // Newer kitty versions also accept `family="Fira Code" style=Retina`
+    let family = match family.strip_prefix("family=") {
+        Some(spec) => match spec.strip_prefix('"') {
+            Some(quoted) => quoted.split('"').next().unwrap_or(quoted).to_string(),
+            None => spec.split_whitespace().next().unwrap_or(spec).to_string(),
+        },
+        None => family,
+    };
+    font_with_size(&family, size.as_deref())
+}
+
+fn foot_font() -> Option<String> {
+    let contents = fs::read_to_string(config_home()?.join("foot/foot.ini")).ok()?;
+    let font = ini_value(&contents, Some("main"), "font")
+        .or_else(|| ini_value(&contents, None, "font"))?;
+    parse_fontconfig_pattern(&font)
+}
+
+/// The first font of a comma-separated fontconfig list such as
+/// `Fira Code:size=11,Noto Color Emoji`, as "Fira Code 11"
+fn parse_fontconfig_pattern(pattern: &str) -> Option<String> {
+    let first = pattern.split(',').next()?.trim();
+    let first = first.strip_prefix("xft:").unwrap_or(first);
+    let mut attributes = first.split(':');
+    let family = attributes.next()?;
+
+    let size = attributes.find_map(|attribute| {
+        let (key, value) = attribute.split_once('=')?;
+        match key {
+            "size" => Some(value.to_string()),
+            "pixelsize" => Some(format!("{}px", value)),
+            _ => None,
+        }
+    });
+    font_with_size(family, size.as_deref())
+}
+
+fn wezterm_font() -> Option<String> {
+    let home = dirs::home_dir()?;
+    let (_, contents) = read_first(&[
+        config_home()?.join("wezterm/wezterm.lua"),
+        home.join(".wezterm.lua"),
+    ])?;
+    parse_wezterm(&contents)
+}
+
+/// Best-effort scan of a Lua config for `wezterm.font("Name")`,
+/// `wezterm.font_with_fallback { "Name", ... }` and `font_size = 12.0`
+fn parse_wezterm(contents: &str) -> Option<String> {
+    let code: String = contents
+        .lines()
+        .map(|line| line.split("--").next().unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let after_font = &code[code.find("wezterm.font")?..];
+    let family = after_font.split(['"', '\'']).nth(1)?;
+
+    let size = code.split("font_size").nth(1).and_then(|rest| {
+        let value = rest.trim_start().strip_prefix('=')?.trim_start();
+        let end = value
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(value.len());
+        Some(value[..end].trim_end_matches(".0").to_string())
+    });
+
+    font_with_size(family, size.as_deref())
+}
+
+fn xresources_font(class: &str) -> Option<String> {
+    let resources = command_output("xrdb", &["-query"]).or_else(|| {
+        let home = dirs::home_dir()?;
+        read_first(&[home.join(".Xresources"), home.join(".Xdefaults")])
+            .map(|(_, contents)| contents)
+    })?;
+    parse_xresources(&resources, class)
+}
+
+/// Font resources for `class` (xterm or urxvt), or for any class (`*font`)
+fn parse_xresources(resources: &str, class: &str) -> Option<String> {
+    let mut face_name = None;
+    let mut face_size = None;
+    let mut font = None;
+
+    for line in resources.lines() {
+        let line = line.trim();
+        if line.starts_with('!') {
+            continue;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let name = name.trim().to_lowercase();
+        if !(name.starts_with(class) || name.starts_with('*')) {
+            continue;
+        }
+        let value = value.trim().to_string();
+
+        match name.rsplit(['*', '.']).next() {
+            Some("facename") => face_name = Some(value),
+            Some("facesize") => face_size = Some(value),
+            Some("font") => font = Some(value),
+            _ => {}
+        }
+    }
+
+    match (face_name, font) {
+        (Some(face_name), _) => {
+            let face_name = face_name.strip_prefix("xft:").unwrap_or(&face_name);
+            match parse_fontconfig_pattern(face_name) {
+                Some(font) if face_size.is_none() => Some(font),
+                _ => font_with_size(face_name.split(':').next()?, face_size.as_deref()),
+            }
+        }
+        (None, Some(font)) if font.starts_with("xft:") => parse_fontconfig_pattern(&font),
+        // Core X fonts: -misc-fixed-medium-r-normal--13-...
+        (None, Some(font)) if font.starts_with('-') => {
+            let fields: Vec<&str> = font.split('-').collect();
+            font_with_size(
+                fields.get(2)?,
+                fields.get(7).filter(|size| **size != "*").copied(),
+            )
+        }
+        (None, Some(font)) => font_with_size(&font, None),
+        (None, None) => None,
+    }
+}
+
+fn konsole_font() -> Option<String> {
+    let konsolerc = fs::read_to_string(config_home()?.join("konsolerc")).ok()?;
+    let profile = ini_value(&konsolerc, Some("Desktop Entry"), "DefaultProfile")?;
+
+    let data = dirs::data_dir()?;
+    let profile = fs::read_to_string(data.join("konsole").join(profile)).ok()?;
+    parse_konsole_font(&ini_value(&profile, Some("Appearance"), "Font")?)
+}
+
+/// Qt font descriptions: `Hack,10,-1,5,50,0,0,0,0,0`
+fn parse_konsole_font(font: &str) -> Option<String> {
+    let mut fields = font.split(',');
+    let family = fields.next()?;
+    font_with_size(family, fields.next())
+}
+
+fn gnome_terminal_font() -> Option<String> {
+    let dump = command_output("dconf", &["dump", "/org/gnome/terminal/legacy/profiles:/"]);
+    if let Some(font) = dump.as_deref().and_then(parse_gnome_terminal) {
+        return Some(font);
+    }
+
+    // Profiles use the system monospace font unless they set their own
+    let system = command_output(
+        "gsettings",
+        &["get", "org.gnome.desktop.interface", "monospace-font-name"],
+    )?;
+    font_with_size(unquote(&system), None)
+}
+
+/// The custom font of the default profile in a `dconf dump` of
+/// `/org/gnome/terminal/legacy/profiles:/`
+fn parse_gnome_terminal(dump: &str) -> Option<String> {
+    let profile = match ini_value(dump, Some("/"), "default") {
+        Some(uuid) => format!(":{}", uuid),
+        // Without a default, the only profile present is used
+        None => dump
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("[:")?.strip_suffix(']'))
+            .map(|uuid| format!(":{}", uuid))?,
+    };
+
+    let use_system_font = ini_value(dump, Some(&profile), "use-system-font");
+    if use_system_font.as_deref() != Some("false") {
+        return None;
+    }
+    font_with_size(&ini_value(dump, Some(&profile), "font")?, None)
+}
+
+fn apple_terminal_font() -> Option<String> {
+    let query = |property: &str| {
+        command_output(
+            "osascript",
+            &[
+                "-e",
+                &format!(
+                    "tell application \"Terminal\" to {} of window frontmost",
+                    property
+                ),
+            ],
+        )
+        .map(|output| output.trim().to_string())
+        .filter(|output| !output.is_empty())
+    };
+
+    let name = query("font name")?;
+    font_with_size(&name, query("font size").as_deref())
+}
+
+fn iterm2_font() -> Option<String> {
+    let bookmarks = command_output(
+        "defaults",
+        &["read", "com.googlecode.iterm2", "New Bookmarks"],
+    )?;
+    parse_iterm2(&bookmarks)
+}
+
+/// The first profile's `"Normal Font" = "MesloLGS-NF-Regular 13";`
+fn parse_iterm2(bookmarks: &str) -> Option<String> {
+    bookmarks.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("\"Normal Font\"")?;
+        let value = value.trim_start().strip_prefix('=')?.trim();
+        Some(unquote(value.trim_end_matches(';')).to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_terminal_configs() {
+        assert_eq!(
+            parse_alacritty_toml("[font]\nsize = 11.5\n[font.normal]\nfamily = \"Hack\"\n"),
+            Some("Hack 11.5".to_string())
+        );
+        assert_eq!(
+            parse_alacritty_yaml(
+                "window:\n  opacity: 0.9\nfont:\n  bold:\n    family: Bold Font\n  normal:\n    family: \"Iosevka\"\n    style: Regular\n  size: 12\n"
+            ),
+            Some("Iosevka 12".to_string())
+        );
+        assert_eq!(
+            parse_kitty("# font_family Nope\nfont_family      JetBrains Mono\nfont_size 10.0\n"),
+            Some("JetBrains Mono 10.0".to_string())
+        );
+        assert_eq!(
+            parse_kitty("font_family family=\"Fira Code\" style=Retina\n"),
+            Some("Fira Code".to_string())
+        );
+        assert_eq!(
+            parse_fontconfig_pattern("Fira Code:size=11:weight=bold,Noto Color Emoji"),
+            Some("Fira Code 11".to_string())
+        );
+        assert_eq!(
+            parse_wezterm(
+                "-- config.font = wezterm.font 'Nope'\nconfig.font = wezterm.font_with_fallback { 'Cascadia Code', 'Symbols' }\nconfig.font_size = 13.0\n"
+            ),
+            Some("Cascadia Code 13".to_string())
+        );
+        assert_eq!(
+            parse_konsole_font("Hack,10,-1,5,50,0,0,0,0,0"),
+            Some("Hack 10".to_string())
+        );
+        assert_eq!(
+            parse_iterm2(
+                "    \"Name\" = Default;\n    \"Normal Font\" = \"MesloLGS-NF-Regular 13\";\n"
+            ),
+            Some("MesloLGS-NF-Regular 13".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_xresources() {
+        let resources = "! comment\nXTerm*faceName: DejaVu Sans Mono\nxterm*faceSize: 11\nURxvt.font: xft:Terminus:size=12,xft:Symbols\n";
+        assert_eq!(
+            parse_xresources(resources, "xterm"),
+            Some("DejaVu Sans Mono 11".to_string())
+        );
+        assert_eq!(
+            parse_xresources(resources, "urxvt"),
+            Some("Terminus 12".to_string())
+        );
+        assert_eq!(
+            parse_xresources(
+                "*font: -misc-fixed-medium-r-normal--13-120-75-75-c-70-iso10646-1\n",
+                "xterm"
+            ),
+            Some("fixed 13".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_gnome_terminal() {
+        let dump = "[/]\ndefault='b1dcc9dd'\nlist=['b1dcc9dd']\n\n[:b1dcc9dd]\nfont='Fira Code 12'\nuse-system-font=false\n";
+        assert_eq!(parse_gnome_terminal(dump), Some("Fira Code 12".to_string()));
+        assert_eq!(
+            parse_gnome_terminal(&dump.replace("use-system-font=false", "")),
+            None
+        );
+    }
+}
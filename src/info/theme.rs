@@ -0,0 +1,276 @@
+use super::FieldValue;
+use crate::utils::config_files::{command_output, config_home, font_with_size, ini_value, unquote};
+use anyhow::Result;
+use std::fs;
+use std::path::PathBuf;
+
+/// A setting read from each toolkit's configuration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Setting {
+    Theme,
+    Icons,
+    Cursor,
+    Font,
+}
+
+/// A toolkit and the value it's configured with
+#[derive(Debug, Clone, PartialEq)]
+struct Source {
+    toolkit: &'static str,
+    value: String,
+}
+
+impl Setting {
+    /// Key in GTK's `settings.ini` and `.gtkrc-2.0`
+    fn gtk_key(self) -> &'static str {
+        match self {
+            Setting::Theme => "gtk-theme-name",
+            Setting::Icons => "gtk-icon-theme-name",
+            Setting::Cursor => "gtk-cursor-theme-name",
+            Setting::Font => "gtk-font-name",
+        }
+    }
+
+    /// Key under `org.gnome.desktop.interface`, which GNOME uses instead of
+    /// `settings.ini`
+    fn gsettings_key(self) -> &'static str {
+        match self {
+            Setting::Theme => "gtk-theme",
+            Setting::Icons => "icon-theme",
+            Setting::Cursor => "cursor-theme",
+            Setting::Font => "font-name",
+        }
+    }
+}
+
+pub fn get_theme() -> Result<FieldValue> {
+    Ok(setting_field(Setting::Theme))
+}
+
+pub fn get_icons() -> Result<FieldValue> {
+    Ok(setting_field(Setting::Icons))
+}
+
+pub fn get_cursor() -> Result<FieldValue> {
+    Ok(setting_field(Setting::Cursor))
+}
+
+pub fn get_font() -> Result<FieldValue> {
+    Ok(setting_field(Setting::Font))
+}
+
+/// The merged value, e.g. "Breeze [Qt], Adwaita [GTK2/3]", with each
+/// toolkit's value as a part
+fn setting_field(setting: Setting) -> FieldValue {
+    let sources = read_sources(setting);
+    if sources.is_empty() {
+        return "Unknown".to_string().into();
+    }
+
+    sources.iter().fold(
+        FieldValue::from(merge_sources(&sources)),
+        |field, source| {
+            let part = match source.toolkit {
+                "GTK2" => "gtk2",
+                "GTK3" => "gtk3",
+                "GTK4" => "gtk4",
+                "X11" => "x11",
+                _ => "qt",
+            };
+            field.with_part(part, &source.value)
+        },
+    )
+}
+
+fn read_sources(setting: Setting) -> Vec<Source> {
+    let mut values = vec![
+        ("Qt", qt_setting(setting)),
+        ("GTK2", gtk2_setting(setting)),
+        (
+            "GTK3",
+            gtk_settings_ini("gtk-3.0", setting).or_else(|| gsettings(setting)),
+        ),
+        ("GTK4", gtk_settings_ini("gtk-4.0", setting)),
+    ];
+
+    // The default cursor theme applies to X applications of any toolkit
+    if setting == Setting::Cursor && values.iter().all(|(_, value)| value.is_none()) {
+        values.push(("X11", default_cursor()));
+    }
+
+    values
+        .into_iter()
+        .filter_map(|(toolkit, value)| {
+            let value = value.filter(|value| !value.is_empty())?;
+            Some(Source { toolkit, value })
+        })
+        .collect()
+}
+
+/// Group toolkits sharing a value the way neofetch does: "Adwaita [GTK2/3]"
+fn merge_sources(sources: &[Source]) -> String {
+    let mut groups: Vec<(&str, Vec<&str>)> = Vec::new();
+    for source in sources {
+        match groups.iter_mut().find(|(value, _)| *value == source.value) {
+            Some((_, toolkits)) => toolkits.push(source.toolkit),
+            None => groups.push((&source.value, vec![source.toolkit])),
+        }
+    }
+
+    groups
+        .iter()
+        .map(|(value, toolkits)| {
+            let gtk: Vec<&str> = toolkits
+                .iter()
+                .filter_map(|toolkit| toolkit.strip_prefix("GTK"))
+                .collect();
+            let mut labels: Vec<String> = toolkits
+                .iter()
+                .filter(|toolkit| !toolkit.starts_with("GTK"))
+                .map(|toolkit| toolkit.to_string())
+                .collect();
+            if !gtk.is_empty() {
+                labels.push(format!("GTK{}", gtk.join("/")));
+            }
+            format!("{} [{}]", value, labels.join("/"))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn gtk_settings_ini(dir: &str, setting: Setting) -> Option<String> {
+    let path = config_home()?.join(dir).join("settings.ini");
+    ini_value(
+        &fs::read_to_string(path).ok()?,
+        Some("Settings"),
+        setting.gtk_key(),
+    )
+}
+
+fn gtk2_setting(setting: Setting) -> Option<String> {
+    let path = std::env::var_os("GTK2_RC_FILES")
+        .and_then(|files| std::env::split_paths(&files).next())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".gtkrc-2.0")))?;
+    ini_value(&fs::read_to_string(path).ok()?, None, setting.gtk_key())
+}
+
+/// GNOME's settings, which it doesn't mirror to `settings.ini`. Other
+/// desktops would only get gsettings' defaults.
+fn gsettings(setting: Setting) -> Option<String> {
+    let desktop = std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default();
+    if !desktop.contains("GNOME") {
+        return None;
+    }
+    let output = command_output(
+        "gsettings",
+        &[
+            "get",
+            "org.gnome.desktop.interface",
+            setting.gsettings_key(),
+        ],
+    )?;
+    Some(unquote(&output).to_string())
+}
+
+/// Qt settings from qt5ct/qt6ct when they're the platform theme, otherwise
+/// from KDE's configuration
+fn qt_setting(setting: Setting) -> Option<String> {
+    let platform_theme = std::env::var("QT_QPA_PLATFORMTHEME").unwrap_or_default();
+    let qtct = || qtct_setting("qt6ct", setting).or_else(|| qtct_setting("qt5ct", setting));
+    if platform_theme.starts_with("qt") && platform_theme.ends_with("ct") {
+        qtct().or_else(|| kde_setting(setting))
+    } else {
+        kde_setting(setting).or_else(qtct)
+    }
+}
+
+fn qtct_setting(name: &str, setting: Setting) -> Option<String> {
+    let path = config_home()?.join(name).join(format!("{}.conf", name));
+    let contents = fs::read_to_string(path).ok()?;
+    match setting {
+        Setting::Theme => ini_value(&contents, Some("Appearance"), "style"),
+        Setting::Icons => ini_value(&contents, Some("Appearance"), "icon_theme"),
+        Setting::Font => parse_qt_font(&ini_value(&contents, Some("Fonts"), "general")?),
+        // qtct leaves the cursor to the desktop
+        Setting::Cursor => None,
+    }
+}
+
+fn kde_setting(setting: Setting) -> Option<String> {
+    let config = config_home()?;
+    let read = |file: &str| fs::read_to_string(config.join(file)).ok();
+    match setting {
+        Setting::Theme => ini_value(&read("kdeglobals")?, Some("KDE"), "widgetStyle"),
+        Setting::Icons => ini_value(&read("kdeglobals")?, Some("Icons"), "Theme"),
+        Setting::Font => parse_qt_font(&ini_value(&read("kdeglobals")?, Some("General"), "font")?),
+        Setting::Cursor => ini_value(&read("kcminputrc")?, Some("Mouse"), "cursorTheme"),
+    }
+}
+
+/// Qt font descriptions: `Noto Sans,10,-1,5,50,0,0,0,0,0` becomes "Noto Sans 10".
+/// qt6ct may store the font as a binary `@Variant(...)`, which is skipped.
+fn parse_qt_font(font: &str) -> Option<String> {
+    if font.starts_with("@Variant") {
+        return None;
+    }
+    let mut fields = font.split(',');
+    font_with_size(fields.next()?, fields.next())
+}
+
+/// The theme `~/.icons/default/index.theme` inherits, which X uses as the
+/// default cursor
+fn default_cursor() -> Option<String> {
+    let paths: Vec<PathBuf> = [
+        dirs::home_dir().map(|home| home.join(".icons")),
+        dirs::data_dir().map(|data| data.join("icons")),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|dir| dir.join("default").join("index.theme"))
+    .collect();
+
+    paths.iter().find_map(|path| {
+        ini_value(
+            &fs::read_to_string(path).ok()?,
+            Some("Icon Theme"),
+            "Inherits",
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_sources() {
+        let source = |toolkit, value: &str| Source {
+            toolkit,
+            value: value.to_string(),
+        };
+
+        assert_eq!(
+            merge_sources(&[source("GTK2", "Adwaita"), source("GTK3", "Adwaita")]),
+            "Adwaita [GTK2/3]"
+        );
+        assert_eq!(
+            merge_sources(&[
+                source("Qt", "Breeze"),
+                source("GTK2", "Breeze"),
+                source("GTK3", "Adwaita-dark"),
+                source("GTK4", "Adwaita-dark"),
+            ]),
+            "Breeze [Qt/GTK2], Adwaita-dark [GTK3/4]"
+        );
+    }
+
+    #[test]
+    fn test_parse_qt_font() {
+        assert_eq!(
+            parse_qt_font("Noto Sans,10,-1,5,50,0,0,0,0,0").as_deref(),
+            Some("Noto Sans 10")
+        );
+        assert_eq!(parse_qt_font("Inter").as_deref(), Some("Inter"));
+        assert_eq!(parse_qt_font("@Variant(\\0\\0\\0@)"), None);
+    }
+}
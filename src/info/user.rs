@@ -0,0 +1,311 @@
+use super::FieldValue;
+use crate::config::DateTimeConfig;
+use crate::utils::time;
+use anyhow::{bail, Context, Result};
+use std::env;
+#[cfg(unix)]
+use std::fs;
+use sysinfo::System;
+
+/// Locale categories that can override `$LANG`
+const LOCALE_CATEGORIES: &[&str] = &[
+    "LC_CTYPE",
+    "LC_NUMERIC",
+    "LC_TIME",
+    "LC_COLLATE",
+    "LC_MONETARY",
+    "LC_MESSAGES",
+    "LC_PAPER",
+    "LC_NAME",
+    "LC_ADDRESS",
+    "LC_TELEPHONE",
+    "LC_MEASUREMENT",
+    "LC_IDENTIFICATION",
+];
+
+/// The user rice runs as, by user id so it works under cron and in
+/// containers where `$USER` isn't set
+#[cfg(unix)]
+pub fn username() -> String {
+    // SAFETY: getuid can't fail and has no side effects
+    let uid = unsafe { libc::getuid() };
+    passwd_name(uid)
+        .or_else(|| env::var("USER").ok())
+        // Containers often run as an id without a passwd entry
+        .unwrap_or_else(|| uid.to_string())
+}
+
+#[cfg(not(unix))]
+pub fn username() -> String {
+    env::var("USERNAME")
+        .or_else(|_| env::var("USER"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// The name for `uid` from the user database, which besides `/etc/passwd`
+/// covers LDAP, systemd-homed and other NSS sources
+#[cfg(unix)]
+fn passwd_name(uid: libc::uid_t) -> Option<String> {
+    let mut buffer: Vec<libc::c_char> = vec![0; 1024];
+    loop {
+        // SAFETY: getpwuid_r fills the zeroed passwd struct with pointers into
+        // `buffer`, which outlives every use of them below
+        unsafe {
+            let mut passwd: libc::passwd = std::mem::zeroed();
+            let mut result = std::ptr::null_mut();
+            let status = libc::getpwuid_r(
+                uid,
+                &mut passwd,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut result,
+            );
+            if status == libc::ERANGE && buffer.len() < 1 << 20 {
+                buffer.resize(buffer.len() * 2, 0);
+                continue;
+            }
+            if status != 0 || result.is_null() || passwd.pw_name.is_null() {
+                return None;
+            }
+            let name = std::ffi::CStr::from_ptr(passwd.pw_name).to_string_lossy();
+            return Some(name.to_string()).filter(|name| !name.is_empty());
+        }
+    }
+}
+
+pub fn get_locale() -> Result<FieldValue> {
+    let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+    let overrides: Vec<(&str, String)> = LOCALE_CATEGORIES
+        .iter()
+        .filter_map(|category| Some((*category, var(category)?)))
+        .collect();
+
+    Ok(describe_locale(
+        var("LC_ALL").as_deref(),
+        var("LANG").as_deref(),
+        &overrides,
+    ))
+}
+
+/// `$LC_ALL` wins outright; otherwise `$LANG` along with any categories set
+/// to something else, e.g. "en_US.UTF-8 (LC_TIME=de_DE.UTF-8)"
+fn describe_locale(
+    all: Option<&str>,
+    lang: Option<&str>,
+    overrides: &[(&str, String)],
+) -> FieldValue {
+    if let Some(all) = all {
+        return FieldValue::from(all.to_string()).with_part("lang", all);
+    }
+
+    // Without any locale variables, programs use the POSIX locale
+    let lang = lang.unwrap_or("C");
+    let differing: Vec<String> = overrides
+        .iter()
+        .filter(|(_, value)| value != lang)
+        .map(|(category, value)| format!("{}={}", category, value))
+        .collect();
+
+    let value = if differing.is_empty() {
+        lang.to_string()
+    } else {
+        format!("{} ({})", lang, differing.join(", "))
+    };
+    FieldValue::from(value).with_part("lang", lang)
+}
+
+pub fn get_timezone() -> Result<FieldValue> {
+    let name = timezone_name();
+    let (abbreviation, offset) = time::zone_at(time::now()).unwrap_or_default();
+    let offset = time::format_offset(offset);
+
+    let details = if abbreviation.is_empty() || offset.ends_with(&abbreviation) {
+        offset.clone()
+    } else {
+        format!("{}, {}", abbreviation, offset)
+    };
+    let value = match &name {
+        Some(name) if *name != details => format!("{} ({})", name, details),
+        _ => details,
+    };
+
+    let mut field = FieldValue::from(value).with_part("offset", offset);
+    if let Some(name) = name {
+        field = field.with_part("name", name);
+    }
+    if !abbreviation.is_empty() {
+        field = field.with_part("abbreviation", abbreviation);
+    }
+    Ok(field)
+}
+
+/// The zone name from `$TZ` or the zoneinfo file `/etc/localtime` links to,
+/// e.g. "Europe/Berlin"
+fn timezone_name() -> Option<String> {
+    if let Ok(tz) = env::var("TZ") {
+        let tz = tz.trim_start_matches(':');
+        if !tz.is_empty() {
+            return Some(zone_from_path(tz).unwrap_or_else(|| tz.to_string()));
+        }
+    }
+
+    #[cfg(unix)]
+    {
+        if let Ok(target) = fs::read_link("/etc/localtime") {
+            if let Some(zone) = zone_from_path(&target.to_string_lossy()) {
+                return Some(zone);
+            }
+        }
+        // Debian keeps the name next to a copied /etc/localtime
+        if let Ok(zone) = fs::read_to_string("/etc/timezone") {
+            let zone = zone.trim();
+            if !zone.is_empty() {
+                return Some(zone.to_string());
+            }
+        }
+    }
+    None
+}
+
+/// "Europe/Berlin" from "/usr/share/zoneinfo/Europe/Berlin", skipping the
+/// `posix/` and `right/` variants of the database
+fn zone_from_path(path: &str) -> Option<String> {
+    let (_, zone) = path.split_once("zoneinfo/")?;
+    let zone = zone
+        .strip_prefix("posix/")
+        .or_else(|| zone.strip_prefix("right/"))
+        .unwrap_or(zone);
+    Some(zone.to_string()).filter(|zone| !zone.is_empty())
+}
+
+/// Logged-in users from utmp, each once with their number of sessions,
+/// e.g. "alice (2), bob"
+pub fn get_users() -> Result<FieldValue> {
+    let sessions = logged_in_users();
+    if sessions.is_empty() {
+        return Ok("None".to_string().into());
+    }
+
+    let users = group_users(&sessions);
+    let value = users
+        .iter()
+        .map(|(name, count)| match count {
+            1 => name.to_string(),
+            _ => format!("{} ({})", name, count),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    Ok(FieldValue::from(value)
+        .with_part("count", users.len())
+        .with_part("sessions", sessions.len()))
+}
+
+/// Names of users with a login session, one per session
+#[cfg(unix)]
+fn logged_in_users() -> Vec<String> {
+    let mut users = Vec::new();
+    // SAFETY: the utmpx functions are only called from this thread, and each
+    // entry is copied out before the next getutxent call reuses its buffer
+    unsafe {
+        libc::setutxent();
+        loop {
+            let entry = libc::getutxent();
+            if entry.is_null() {
+                break;
+            }
+            if (*entry).ut_type != libc::USER_PROCESS {
+                continue;
+            }
+            let name = std::ffi::CStr::from_ptr((*entry).ut_user.as_ptr());
+            let name = name.to_string_lossy().to_string();
+            if !name.is_empty() {
+                users.push(name);
+            }
+        }
+        libc::endutxent();
+    }
+    users
+}
+
+#[cfg(not(unix))]
+fn logged_in_users() -> Vec<String> {
+    Vec::new()
+}
+
+/// Users in order of first login, with their number of sessions
+fn group_users(sessions: &[String]) -> Vec<(&str, usize)> {
+    let mut users: Vec<(&str, usize)> = Vec::new();
+    for name in sessions {
+        match users.iter_mut().find(|(user, _)| user == name) {
+            Some((_, count)) => *count += 1,
+            None => users.push((name, 1)),
+        }
+    }
+    users
+}
+
+pub fn get_boot_time(config: &DateTimeConfig) -> Result<FieldValue> {
+    let boot_time = System::boot_time();
+    if boot_time == 0 {
+        bail!("Boot time not available");
+    }
+    let value = time::format_local(boot_time as i64, &config.boot_format)
+        .context("Failed to format boot time")?;
+    Ok(FieldValue::from(value).with_part("timestamp", boot_time))
+}
+
+pub fn get_datetime(config: &DateTimeConfig) -> Result<FieldValue> {
+    let now = time::now();
+    let value = time::format_local(now, &config.format).context("Failed to format the time")?;
+    Ok(FieldValue::from(value).with_part("timestamp", now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_passwd_name() {
+        assert_eq!(passwd_name(0).as_deref(), Some("root"));
+    }
+
+    #[test]
+    fn test_describe_locale() {
+        let overrides = vec![
+            ("LC_CTYPE", "en_US.UTF-8".to_string()),
+            ("LC_TIME", "de_DE.UTF-8".to_string()),
+        ];
+        assert_eq!(
+            describe_locale(None, Some("en_US.UTF-8"), &overrides).value,
+            "en_US.UTF-8 (LC_TIME=de_DE.UTF-8)"
+        );
+        assert_eq!(
+            describe_locale(Some("C.UTF-8"), Some("en_US.UTF-8"), &overrides).value,
+            "C.UTF-8"
+        );
+        assert_eq!(describe_locale(None, None, &[]).value, "C");
+    }
+
+    #[test]
+    fn test_zone_from_path() {
+        assert_eq!(
+            zone_from_path("/usr/share/zoneinfo/Europe/Berlin").as_deref(),
+            Some("Europe/Berlin")
+        );
+        assert_eq!(
+            zone_from_path("../usr/share/zoneinfo/posix/America/New_York").as_deref(),
+            Some("America/New_York")
+        );
+        assert_eq!(zone_from_path("/etc/localtime"), None);
+    }
+
+    #[test]
+    fn test_group_users() {
+        let sessions: Vec<String> = ["alice", "bob", "alice"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        assert_eq!(group_users(&sessions), vec![("alice", 2), ("bob", 1)]);
+    }
+}
@@ -0,0 +1,212 @@
+use super::FieldValue;
+use anyhow::Result;
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use std::path::Path;
+
+/// Hypervisors by the vendor or product name they give the virtual machine's
+/// firmware, checked in order
+#[cfg(any(target_os = "linux", test))]
+const HYPERVISORS: &[(&str, &str)] = &[
+    ("KVM", "KVM"),
+    ("QEMU", "QEMU"),
+    ("VMware", "VMware"),
+    ("VirtualBox", "VirtualBox"),
+    ("innotek", "VirtualBox"),
+    ("Virtual Machine", "Hyper-V"),
+    ("Hyper-V", "Hyper-V"),
+    ("Xen", "Xen"),
+    ("Parallels", "Parallels"),
+    ("bhyve", "bhyve"),
+];
+
+/// Container managers by the name they use in `$container`,
+/// `/run/systemd/container` and cgroup paths
+#[cfg(any(target_os = "linux", test))]
+const CONTAINERS: &[(&str, &str)] = &[
+    ("docker", "Docker"),
+    ("podman", "Podman"),
+    ("libpod", "Podman"),
+    ("lxc", "LXC"),
+    ("systemd-nspawn", "systemd-nspawn"),
+    ("machine.slice", "systemd-nspawn"),
+    ("kubepods", "Kubernetes"),
+];
+
+pub fn get_virtualization() -> Result<FieldValue> {
+    let container = detect_container();
+    let vm = detect_vm();
+
+    let value = match (&container, &vm) {
+        (Some(container), Some(vm)) => format!("{} on {}", container, vm),
+        (Some(name), None) | (None, Some(name)) => name.clone(),
+        (None, None) => "None".to_string(),
+    };
+
+    let mut field = FieldValue::from(value);
+    if let Some(container) = container {
+        field = field.with_part("container", container);
+    }
+    if let Some(vm) = vm {
+        field = field.with_part("vm", vm);
+    }
+    Ok(field)
+}
+
+/// The container rice runs in, from the marker files container managers
+/// create, the `container` variable they set, or the cgroup of init
+#[cfg(target_os = "linux")]
+pub fn detect_container() -> Option<String> {
+    if Path::new("/.dockerenv").exists() {
+        return Some("Docker".to_string());
+    }
+    if Path::new("/run/.containerenv").exists() {
+        return Some("Podman".to_string());
+    }
+
+    let variable = fs::read_to_string("/run/systemd/container")
+        .ok()
+        .or_else(|| std::env::var("container").ok())
+        .or_else(|| {
+            let environ = fs::read("/proc/1/environ").ok()?;
+            environ
+                .split(|&b| b == 0)
+                .find_map(|entry| entry.strip_prefix(b"container="))
+                .map(|value| String::from_utf8_lossy(value).to_string())
+        });
+    if let Some(name) = variable.map(|name| name.trim().to_string()) {
+        if !name.is_empty() {
+            return Some(container_name(&name).unwrap_or(name));
+        }
+    }
+
+    fs::read_to_string("/proc/1/cgroup")
+        .ok()
+        .and_then(|cgroup| container_name(&cgroup))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn detect_container() -> Option<String> {
+    None
+}
+
+/// The display name of the first container manager mentioned in `text`
+#[cfg(any(target_os = "linux", test))]
+fn container_name(text: &str) -> Option<String> {
+    CONTAINERS
+        .iter()
+        .find(|(key, _)| text.contains(key))
+        .map(|(_, name)| name.to_string())
+}
+
+/// The hypervisor rice runs under: WSL from the kernel release, then the
+/// firmware's DMI names, then the CPU's hypervisor flag
+#[cfg(target_os = "linux")]
+pub fn detect_vm() -> Option<String> {
+    let release = fs::read_to_string("/proc/sys/kernel/osrelease").unwrap_or_default();
+    if let Some(wsl) = wsl_version(&release) {
+        return Some(wsl.to_string());
+    }
+
+    let dmi: Vec<String> = ["sys_vendor", "product_name", "board_vendor", "bios_vendor"]
+        .iter()
+        .filter_map(|file| fs::read_to_string(Path::new("/sys/class/dmi/id").join(file)).ok())
+        .collect();
+    if let Some(name) = hypervisor_name(&dmi.join("\n")) {
+        return Some(name);
+    }
+
+    if fs::read_to_string("/sys/hypervisor/type").is_ok_and(|kind| kind.trim() == "xen") {
+        return Some("Xen".to_string());
+    }
+
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+    let flagged = cpuinfo
+        .lines()
+        .filter(|line| line.starts_with("flags"))
+        .any(|line| line.split_whitespace().any(|flag| flag == "hypervisor"));
+    flagged.then(|| "Virtual machine".to_string())
+}
+
+#[cfg(target_os = "macos")]
+pub fn detect_vm() -> Option<String> {
+    let output = std::process::Command::new("sysctl")
+        .args(["-n", "kern.hv_vmm_present"])
+        .output()
+        .ok()?;
+    (String::from_utf8_lossy(&output.stdout).trim() == "1").then(|| "Virtual machine".to_string())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn detect_vm() -> Option<String> {
+    None
+}
+
+/// "WSL2" for `5.15.90.1-microsoft-standard-WSL2`, "WSL" for WSL 1's
+/// `4.4.0-19041-Microsoft`
+#[cfg(any(target_os = "linux", test))]
+fn wsl_version(release: &str) -> Option<&'static str> {
+    if release.contains("WSL2") {
+        Some("WSL2")
+    } else if release.to_lowercase().contains("microsoft") {
+        Some("WSL")
+    } else {
+        None
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn hypervisor_name(dmi: &str) -> Option<String> {
+    HYPERVISORS
+        .iter()
+        .find(|(key, _)| dmi.contains(key))
+        .map(|(_, name)| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_container_name() {
+        assert_eq!(
+            container_name("12:memory:/docker/3f1c2a\n0::/").as_deref(),
+            Some("Docker")
+        );
+        assert_eq!(
+            container_name("0::/lxc.payload.web").as_deref(),
+            Some("LXC")
+        );
+        assert_eq!(
+            container_name("0::/machine.slice/machine-dev.scope").as_deref(),
+            Some("systemd-nspawn")
+        );
+        assert_eq!(container_name("0::/init.scope"), None);
+    }
+
+    #[test]
+    fn test_detect_vm_names() {
+        assert_eq!(
+            wsl_version("5.15.90.1-microsoft-standard-WSL2"),
+            Some("WSL2")
+        );
+        assert_eq!(wsl_version("4.4.0-19041-Microsoft"), Some("WSL"));
+        assert_eq!(wsl_version("6.6.8-arch1-1"), None);
+
+        let dmi = |names: &[&str]| hypervisor_name(&names.join("\n"));
+        assert_eq!(
+            dmi(&["QEMU", "Standard PC (Q35 + ICH9, 2009)"]).as_deref(),
+            Some("QEMU")
+        );
+        assert_eq!(
+            dmi(&["innotek GmbH", "VirtualBox"]).as_deref(),
+            Some("VirtualBox")
+        );
+        assert_eq!(
+            dmi(&["Microsoft Corporation", "Virtual Machine"]).as_deref(),
+            Some("Hyper-V")
+        );
+        assert_eq!(dmi(&["LENOVO", "20XW0055US"]), None);
+    }
+}
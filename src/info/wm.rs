@@ -0,0 +1,253 @@
+use super::FieldValue;
+use anyhow::Result;
+#[cfg(not(target_os = "macos"))]
+use std::env;
+#[cfg(all(unix, not(target_os = "macos")))]
+use std::fs;
+#[cfg(all(unix, not(target_os = "macos")))]
+use std::path::PathBuf;
+#[cfg(target_os = "macos")]
+use std::process::Command;
+
+/// Wayland compositor binaries and their display names
+#[cfg(all(unix, not(target_os = "macos")))]
+const COMPOSITORS: &[(&str, &str)] = &[
+    ("sway", "Sway"),
+    ("Hyprland", "Hyprland"),
+    ("river", "river"),
+    ("niri", "niri"),
+    ("kwin_wayland", "KWin"),
+    ("gnome-shell", "Mutter"),
+    ("mutter", "Mutter"),
+    ("weston", "Weston"),
+    ("labwc", "labwc"),
+    ("wayfire", "Wayfire"),
+    ("cosmic-comp", "COSMIC"),
+    ("hikari", "hikari"),
+    ("dwl", "dwl"),
+    ("cage", "Cage"),
+    ("gamescope", "Gamescope"),
+    ("qtile", "Qtile"),
+    ("miracle-wm", "Miracle"),
+];
+
+/// Processes that hand the Wayland socket to the real compositor
+#[cfg(all(unix, not(target_os = "macos")))]
+const COMPOSITOR_LAUNCHERS: &[&str] = &["kwin_wayland_wrapper", "systemd", "gdm-wayland-session"];
+
+/// The kind of graphical session rice is running in
+#[cfg(not(target_os = "macos"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionType {
+    Wayland,
+    X11,
+    Tty,
+}
+
+#[cfg(not(target_os = "macos"))]
+impl SessionType {
+    pub fn name(self) -> &'static str {
+        match self {
+            SessionType::Wayland => "Wayland",
+            SessionType::X11 => "X11",
+            SessionType::Tty => "TTY",
+        }
+    }
+}
+
+/// `$XDG_SESSION_TYPE`, or a guess from the display variables when the
+/// session manager doesn't set it
+#[cfg(not(target_os = "macos"))]
+pub fn session_type() -> SessionType {
+    match env::var("XDG_SESSION_TYPE").as_deref() {
+        Ok("wayland") => SessionType::Wayland,
+        Ok("x11") => SessionType::X11,
+        Ok("tty") => SessionType::Tty,
+        _ if env::var_os("WAYLAND_DISPLAY").is_some() => SessionType::Wayland,
+        _ if env::var_os("DISPLAY").is_some() => SessionType::X11,
+        _ => SessionType::Tty,
+    }
+}
+
+pub fn get_session() -> Result<FieldValue> {
+    #[cfg(target_os = "macos")]
+    return Ok("Quartz".to_string().into());
+
+    #[cfg(not(target_os = "macos"))]
+    Ok(session_type().name().to_string().into())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn get_window_manager() -> Result<FieldValue> {
+    let session = session_type();
+
+    let name = match session {
+        // Xwayland windows are managed by the compositor, so only ask X as a last resort
+        SessionType::Wayland => wayland_compositor().or_else(x11_window_manager),
+        SessionType::X11 => x11_window_manager(),
+        SessionType::Tty => None,
+    };
+
+    Ok(name
+        .map(|name| {
+            FieldValue::from(name.clone())
+                .with_part("name", name)
+                .with_part("session", session.name())
+        })
+        .unwrap_or_else(|| "Unknown".to_string().into()))
+}
+
+/// The name the window manager gives its `_NET_SUPPORTING_WM_CHECK` window
+#[cfg(all(unix, not(target_os = "macos")))]
+fn x11_window_manager() -> Option<String> {
+    let mut connection = crate::utils::x11::Connection::connect_env().ok()?;
+    let root = connection.root();
+    let check = connection
+        .get_window_property(root, "_NET_SUPPORTING_WM_CHECK")
+        .ok()??;
+
+    let name = match connection.get_string_property(check, "_NET_WM_NAME") {
+        Ok(Some(name)) if !name.is_empty() => name,
+        _ => connection.get_string_property(check, "WM_NAME").ok()??,
+    };
+    Some(name).filter(|name| !name.is_empty())
+}
+
+/// The compositor is whichever process listens on the `$WAYLAND_DISPLAY` socket
+#[cfg(all(unix, not(target_os = "macos")))]
+fn wayland_compositor() -> Option<String> {
+    use crate::utils::process;
+
+    let display = env::var("WAYLAND_DISPLAY").unwrap_or_else(|_| "wayland-0".to_string());
+    let socket = if display.starts_with('/') {
+        PathBuf::from(display)
+    } else {
+        PathBuf::from(env::var_os("XDG_RUNTIME_DIR")?).join(display)
+    };
+
+    let inodes = listening_inodes(
+        &fs::read_to_string("/proc/net/unix").ok()?,
+        &socket.to_string_lossy(),
+    );
+    let owners: Vec<String> = socket_owners(&inodes)
+        .into_iter()
+        .filter_map(process::read_process)
+        .map(|process| process.program())
+        .collect();
+
+    let program = owners
+        .iter()
+        .find(|program| !COMPOSITOR_LAUNCHERS.contains(&program.as_str()))
+        .or(owners.first())?;
+    let name = COMPOSITORS
+        .iter()
+        .find(|(binary, _)| binary == program)
+        .map_or(program.as_str(), |(_, name)| name);
+    Some(name.to_string())
+}
+
+/// Inodes of sockets bound to `path` in `/proc/net/unix`, whose columns are
+/// `Num RefCount Protocol Flags Type St Inode Path`
+#[cfg(all(unix, not(target_os = "macos")))]
+fn listening_inodes(net_unix: &str, path: &str) -> Vec<u64> {
+    net_unix
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            if columns.get(7) != Some(&path) {
+                return None;
+            }
+            columns.get(6)?.parse().ok()
+        })
+        .collect()
+}
+
+/// Processes holding any of the given socket inodes open. Only processes of
+/// the current user can be inspected, which includes their compositor.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn socket_owners(inodes: &[u64]) -> Vec<u32> {
+    if inodes.is_empty() {
+        return Vec::new();
+    }
+    let targets: Vec<String> = inodes
+        .iter()
+        .map(|inode| format!("socket:[{}]", inode))
+        .collect();
+
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+        .filter(|pid| {
+            let Ok(fds) = fs::read_dir(format!("/proc/{}/fd", pid)) else {
+                return false;
+            };
+            fds.flatten().any(|fd| {
+                fs::read_link(fd.path()).is_ok_and(|link| {
+                    targets
+                        .iter()
+                        .any(|target| link.as_os_str() == target.as_str())
+                })
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+pub fn get_window_manager() -> Result<FieldValue> {
+    // Check for common macOS window managers
+    if let Ok(output) = Command::new("ps").arg("-e").output() {
+        let output_str = String::from_utf8_lossy(&output.stdout);
+
+        // Check for specific window managers in order of preference
+        if output_str.contains("Rectangle") {
+            return Ok("Rectangle".to_string().into());
+        }
+        if output_str.contains("yabai") {
+            return Ok("yabai".to_string().into());
+        }
+        if output_str.contains("Amethyst") {
+            return Ok("Amethyst".to_string().into());
+        }
+        if output_str.contains("Spectacle") {
+            return Ok("Spectacle".to_string().into());
+        }
+        if output_str.contains("chunkwm") {
+            return Ok("chunkwm".to_string().into());
+        }
+        if output_str.contains("kwm") {
+            return Ok("Kwm".to_string().into());
+        }
+    }
+
+    // Default to Quartz Compositor if no other window manager is detected
+    Ok("Quartz Compositor".to_string().into())
+}
+
+#[cfg(not(unix))]
+pub fn get_window_manager() -> Result<FieldValue> {
+    Ok("Unknown".to_string().into())
+}
+
+#[cfg(all(test, unix, not(target_os = "macos")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listening_inodes() {
+        let net_unix = "Num       RefCount Protocol Flags    Type St Inode Path\n\
+            0000000000000000: 00000002 00000000 00010000 0001 01 31337 /run/user/1000/wayland-1\n\
+            0000000000000000: 00000003 00000000 00000000 0001 03 31400 /run/user/1000/wayland-1\n\
+            0000000000000000: 00000002 00000000 00010000 0001 01 20000 /run/user/1000/bus\n\
+            0000000000000000: 00000003 00000000 00000000 0001 03 31500\n";
+
+        assert_eq!(
+            listening_inodes(net_unix, "/run/user/1000/wayland-1"),
+            vec![31337, 31400]
+        );
+        assert!(listening_inodes(net_unix, "/run/user/1000/wayland-0").is_empty());
+    }
+}
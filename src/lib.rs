@@ -3,26 +3,9 @@ pub mod display;
 pub mod info;
 pub mod utils;
 
-/// Format bytes into human readable string
+/// Format bytes into human readable string with the default `[units]` settings
 pub fn format_bytes(bytes: u64) -> String {
-    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-    if bytes == 0 {
-        return "0 B".to_string();
-    }
-
-    let mut size = bytes as f64;
-    let mut unit_index = 0;
-
-    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-        size /= 1024.0;
-        unit_index += 1;
-    }
-
-    if unit_index == 0 {
-        format!("{} {}", size as u64, UNITS[unit_index])
-    } else {
-        format!("{:.1} {}", size, UNITS[unit_index])
-    }
+    utils::units::format_size(bytes, &config::UnitsConfig::default())
 }
 
 /// Format uptime seconds into human readable string
@@ -46,11 +29,11 @@ mod tests {
     fn test_format_bytes() {
         assert_eq!(format_bytes(0), "0 B");
         assert_eq!(format_bytes(512), "512 B");
-        assert_eq!(format_bytes(1024), "1.0 KB");
-        assert_eq!(format_bytes(1536), "1.5 KB");
-        assert_eq!(format_bytes(1048576), "1.0 MB");
-        assert_eq!(format_bytes(1073741824), "1.0 GB");
-        assert_eq!(format_bytes(2147483648), "2.0 GB");
+        assert_eq!(format_bytes(1024), "1.0 KiB");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(1048576), "1.0 MiB");
+        assert_eq!(format_bytes(1073741824), "1.0 GiB");
+        assert_eq!(format_bytes(2147483648), "2.0 GiB");
     }
 
     #[test]
@@ -67,11 +50,11 @@ mod tests {
     #[test]
     fn test_format_bytes_edge_cases() {
         // Test very large values
-        assert_eq!(format_bytes(1099511627776), "1.0 TB");
+        assert_eq!(format_bytes(1099511627776), "1.0 TiB");
 
         // Test values just under threshold
         assert_eq!(format_bytes(1023), "1023 B");
-        assert_eq!(format_bytes(1047552), "1023.0 KB");
+        assert_eq!(format_bytes(1047552), "1023.0 KiB");
     }
 
     #[test]
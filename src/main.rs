@@ -1,7 +1,10 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::{Parser, Subcommand};
 use colored::Colorize;
 use serde::Serialize;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::time::Duration;
 use tracing::Level;
 
 mod config;
@@ -9,9 +12,13 @@ mod display;
 mod info;
 mod utils;
 
-use config::Config;
+use config::{Config, DiskConfig, UnitsConfig};
+use display::output::{self, OutputFormat};
 use display::Display;
-use info::InfoCollector;
+use info::metrics::{self, MetricGroup};
+use info::{hardware, InfoCollector};
+use utils::terminal::ColorMode;
+use utils::units::format_size;
 
 #[derive(Parser)]
 #[command(name = "rice")]
@@ -26,9 +33,9 @@ struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
 
-    /// Output format (text, json)
-    #[arg(short, long, default_value = "text")]
-    format: String,
+    /// Output format
+    #[arg(short, long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
 
     /// Enable verbose logging
     #[arg(long)]
@@ -45,6 +52,18 @@ struct Cli {
     /// Path to image file to display instead of ASCII art
     #[arg(long)]
     image: Option<String>,
+
+    /// Save the rendered output as an SVG or PNG image instead of printing it
+    #[arg(long, value_name = "FILE")]
+    export: Option<PathBuf>,
+
+    /// Keep redrawing the output, refreshing changing values every SECONDS (default: 2)
+    #[arg(long, value_name = "SECONDS", num_args = 0..=1, default_missing_value = "2")]
+    watch: Option<f64>,
+
+    /// When to use colors and terminal escape sequences
+    #[arg(long, global = true, value_enum, default_value_t = ColorMode::Auto)]
+    color: ColorMode,
 }
 
 #[derive(Subcommand)]
@@ -55,6 +74,8 @@ enum Commands {
     Config,
     /// Show version information
     Version,
+    /// List available logos
+    Logos,
     /// Show system information (legacy)
     System,
     /// Show CPU information (legacy)
@@ -113,7 +134,11 @@ fn main() -> Result<()> {
     } else {
         Level::INFO
     };
-    tracing_subscriber::fmt().with_max_level(level).init();
+    let colors = utils::terminal::init_colors(cli.color);
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_ansi(colors)
+        .init();
 
     // Add the random startup message as an info log (if not disabled)
     if !config.display.disable_startup_message {
@@ -129,6 +154,10 @@ fn main() -> Result<()> {
             println!("rice {}", env!("CARGO_PKG_VERSION"));
             return Ok(());
         }
+        Some(Commands::Logos) => {
+            display::ascii_art::list_logos();
+            return Ok(());
+        }
         Some(Commands::System)
         | Some(Commands::Cpu)
         | Some(Commands::Memory)
@@ -161,28 +190,70 @@ fn run_modern_mode(cli: &Cli) -> Result<()> {
         config.ascii_art.path = Some(image_path.clone());
     }
 
+    if cli.format == OutputFormat::Prometheus {
+        let mut sys = sysinfo::System::new();
+        sys.refresh_memory();
+        sys.refresh_cpu_all();
+        hardware::sample_cpu_usage(&mut sys, Duration::from_millis(config.cpu.sample_ms));
+        print_metrics(&sys, MetricGroup::ALL, &config.disk);
+        return Ok(());
+    }
+
+    // Set before collecting, since some values (the color blocks) are colored
+    // as they're collected. Screenshots and HTML pages keep the theme colors
+    // wherever they're written unless colors are turned off through --color or
+    // NO_COLOR, while Markdown is always plain text.
+    if cli.export.is_some() || cli.format == OutputFormat::Html {
+        let colors = utils::terminal::color_preference(cli.color).unwrap_or(true);
+        colored::control::set_override(colors);
+    } else if cli.format == OutputFormat::Markdown {
+        colored::control::set_override(false);
+    }
+
     // Create info collector
-    let collector = InfoCollector::new(
-        config.info.fields.clone(),
-        config.info.custom_commands.clone(),
-    );
+    let collector = InfoCollector::new(display::layout::field_names(&config), &config);
+
+    if let Some(seconds) = cli.watch {
+        if cli.format != OutputFormat::Text || cli.export.is_some() {
+            bail!("--watch only works with the text display");
+        }
+        if !std::io::stdout().is_terminal() {
+            bail!("--watch needs a terminal");
+        }
+        let interval = Duration::try_from_secs_f64(seconds)
+            .ok()
+            .filter(|interval| !interval.is_zero())
+            .context("--watch interval must be a positive number of seconds")?;
+        return display::watch::run(&Display::new(config), &collector, interval);
+    }
 
     // Collect system information
     let info = collector.collect_all()?;
 
+    if let Some(path) = &cli.export {
+        Display::new(config).export(&info, path)?;
+        println!("Saved to {}", path.display());
+        return Ok(());
+    }
+
     // Handle different output formats
-    match cli.format.as_str() {
-        "json" => {
-            let json =
-                serde_json::to_string_pretty(&info).context("Failed to serialize info to JSON")?;
-            println!("{}", json);
-        }
-        _ => {
+    match cli.format {
+        OutputFormat::Text => {
             // Use display engine for formatted output
             let display = Display::new(config);
             let output = display.render(&info)?;
             println!("{}", output);
         }
+        OutputFormat::Markdown => {
+            println!("{}", Display::new(config).render_markdown(&info)?);
+        }
+        OutputFormat::Html => {
+            println!("{}", Display::new(config).render_html(&info)?);
+        }
+        format => {
+            let data = serde_json::to_value(&info).context("Failed to serialize info")?;
+            println!("{}", output::serialize(&data, format, "info")?);
+        }
     }
 
     Ok(())
@@ -195,18 +266,46 @@ fn run_legacy_mode(cli: &Cli) -> Result<()> {
     let mut sys = System::new_all();
     sys.refresh_all();
 
+    let config = Config::load_from_path(cli.config.clone())?;
+    if cli.format == OutputFormat::Prometheus {
+        let groups: &[MetricGroup] = match cli.command {
+            Some(Commands::System) => &[MetricGroup::System, MetricGroup::Cpu, MetricGroup::Memory],
+            Some(Commands::Cpu) => &[MetricGroup::Cpu],
+            Some(Commands::Memory) => &[MetricGroup::Memory],
+            Some(Commands::Disk) => &[MetricGroup::Disk],
+            Some(Commands::Network) => &[MetricGroup::Network],
+            _ => unreachable!(),
+        };
+        // Usage is only meaningful when sampled over a window
+        if groups.contains(&MetricGroup::Cpu) {
+            hardware::sample_cpu_usage(&mut sys, Duration::from_millis(config.cpu.sample_ms));
+        }
+        print_metrics(&sys, groups, &config.disk);
+        return Ok(());
+    }
+    if matches!(cli.command, Some(Commands::Cpu)) {
+        hardware::sample_cpu_usage(&mut sys, Duration::from_millis(config.cpu.sample_ms));
+    }
+
     match cli.command {
-        Some(Commands::System) => show_system_info(&sys, &cli.format)?,
-        Some(Commands::Cpu) => show_cpu_info(&sys, &cli.format)?,
-        Some(Commands::Memory) => show_memory_info(&sys, &cli.format)?,
-        Some(Commands::Disk) => show_disk_info(&sys, &cli.format)?,
-        Some(Commands::Network) => show_network_info(&sys, &cli.format)?,
+        Some(Commands::System) => show_system_info(&sys, cli.format, &config.units)?,
+        Some(Commands::Cpu) => show_cpu_info(&sys, cli.format)?,
+        Some(Commands::Memory) => show_memory_info(&sys, cli.format, &config.units)?,
+        Some(Commands::Disk) => show_disk_info(&sys, cli.format, &config.units)?,
+        Some(Commands::Network) => show_network_info(&sys, cli.format, &config.units)?,
         _ => unreachable!(),
     }
 
     Ok(())
 }
 
+fn print_metrics(sys: &sysinfo::System, groups: &[MetricGroup], disk: &DiskConfig) {
+    print!(
+        "{}",
+        output::prometheus(&metrics::collect_metrics(sys, groups, disk))
+    );
+}
+
 fn generate_config() -> Result<()> {
     let config_path = crate::config::loader::get_config_path()?;
     let mut created = false;
@@ -284,7 +383,11 @@ fn open_in_editor(path: &std::path::Path) -> Result<()> {
 }
 
 // Legacy system info display for backward compatibility
-fn show_system_info(sys: &sysinfo::System, format: &str) -> Result<()> {
+fn show_system_info(
+    sys: &sysinfo::System,
+    format: OutputFormat,
+    units: &UnitsConfig,
+) -> Result<()> {
     use colored::*;
     use sysinfo::System;
 
@@ -333,12 +436,7 @@ fn show_system_info(sys: &sysinfo::System, format: &str) -> Result<()> {
     };
 
     match format {
-        "json" => {
-            let json = serde_json::to_string_pretty(&info)
-                .context("Failed to serialize system info to JSON")?;
-            println!("{}", json);
-        }
-        _ => {
+        OutputFormat::Text => {
             println!("{}", "=== System Information ===".bold().blue());
             println!("OS: {} {}", info.os_name.green(), info.os_version.yellow());
             println!("Hostname: {}", info.hostname.cyan());
@@ -350,8 +448,8 @@ fn show_system_info(sys: &sysinfo::System, format: &str) -> Result<()> {
             );
             println!("CPU Cores: {}", info.cpu_count.to_string().bright_green());
             println!(
-                "Total Memory: {} MB",
-                (info.total_memory / 1024 / 1024).to_string().bright_blue()
+                "Total Memory: {}",
+                format_size(info.total_memory, units).bright_blue()
             );
             println!(
                 "Memory Usage: {}%",
@@ -362,15 +460,19 @@ fn show_system_info(sys: &sysinfo::System, format: &str) -> Result<()> {
                 info.uptime.to_string().bright_yellow()
             );
         }
+        format => {
+            let data = serde_json::to_value(&info).context("Failed to serialize system info")?;
+            println!("{}", output::serialize(&data, format, "system")?);
+        }
     }
 
     Ok(())
 }
 
-fn show_cpu_info(sys: &sysinfo::System, format: &str) -> Result<()> {
+fn show_cpu_info(sys: &sysinfo::System, format: OutputFormat) -> Result<()> {
     let cpus = sys.cpus();
 
-    if format == "json" {
+    if format != OutputFormat::Text {
         let cpu_data: Vec<serde_json::Value> = cpus
             .iter()
             .enumerate()
@@ -384,11 +486,18 @@ fn show_cpu_info(sys: &sysinfo::System, format: &str) -> Result<()> {
             })
             .collect();
 
-        let json = serde_json::to_string_pretty(&cpu_data)
-            .context("Failed to serialize CPU info to JSON")?;
-        println!("{}", json);
+        println!(
+            "{}",
+            output::serialize(&serde_json::Value::from(cpu_data), format, "cpus")?
+        );
     } else {
         println!("{}", "=== CPU Information ===".bold().blue());
+        if let Ok((one, five, fifteen)) = info::system::read_load_average() {
+            println!(
+                "Load average: {}",
+                format!("{:.2} {:.2} {:.2}", one, five, fifteen).yellow()
+            );
+        }
         for (i, cpu) in cpus.iter().enumerate() {
             println!(
                 "Core {}: {}% @ {} MHz - {}",
@@ -403,34 +512,30 @@ fn show_cpu_info(sys: &sysinfo::System, format: &str) -> Result<()> {
     Ok(())
 }
 
-fn show_memory_info(sys: &sysinfo::System, format: &str) -> Result<()> {
+fn show_memory_info(
+    sys: &sysinfo::System,
+    format: OutputFormat,
+    units: &UnitsConfig,
+) -> Result<()> {
     let total = sys.total_memory();
     let used = sys.used_memory();
     let available = sys.available_memory();
     let usage_percent = (used as f64 / total as f64) * 100.0;
 
-    if format == "json" {
+    if format != OutputFormat::Text {
         let mem_data = serde_json::json!({
-            "total_mb": total / 1024 / 1024,
-            "used_mb": used / 1024 / 1024,
-            "available_mb": available / 1024 / 1024,
+            "total_bytes": total,
+            "used_bytes": used,
+            "available_bytes": available,
             "usage_percent": format!("{:.1}", usage_percent)
         });
 
-        let json = serde_json::to_string_pretty(&mem_data)
-            .context("Failed to serialize memory info to JSON")?;
-        println!("{}", json);
+        println!("{}", output::serialize(&mem_data, format, "memory")?);
     } else {
         println!("{}", "=== Memory Information ===".bold().blue());
-        println!(
-            "Total: {} MB",
-            (total / 1024 / 1024).to_string().bright_green()
-        );
-        println!("Used: {} MB", (used / 1024 / 1024).to_string().red());
-        println!(
-            "Available: {} MB",
-            (available / 1024 / 1024).to_string().cyan()
-        );
+        println!("Total: {}", format_size(total, units).bright_green());
+        println!("Used: {}", format_size(used, units).red());
+        println!("Available: {}", format_size(available, units).cyan());
         println!("Usage: {}%", format!("{:.1}", usage_percent).yellow());
 
         // Memory bar visualization
@@ -443,12 +548,12 @@ fn show_memory_info(sys: &sysinfo::System, format: &str) -> Result<()> {
     Ok(())
 }
 
-fn show_disk_info(_sys: &sysinfo::System, format: &str) -> Result<()> {
+fn show_disk_info(_sys: &sysinfo::System, format: OutputFormat, units: &UnitsConfig) -> Result<()> {
     use sysinfo::Disks;
     // Try to access disk information through the new API
     let disks = Disks::new_with_refreshed_list();
 
-    if format == "json" {
+    if format != OutputFormat::Text {
         let disk_data: Vec<serde_json::Value> = disks
             .iter()
             .map(|disk| {
@@ -456,23 +561,24 @@ fn show_disk_info(_sys: &sysinfo::System, format: &str) -> Result<()> {
                     "name": disk.name().to_string_lossy(),
                     "mount_point": disk.mount_point().to_string_lossy(),
                     "file_system": disk.file_system().to_string_lossy(),
-                    "total_space_mb": disk.total_space() / 1024 / 1024,
-                    "available_space_mb": disk.available_space() / 1024 / 1024,
+                    "total_space_bytes": disk.total_space(),
+                    "available_space_bytes": disk.available_space(),
                     "is_removable": disk.is_removable()
                 })
             })
             .collect();
 
-        let json = serde_json::to_string_pretty(&disk_data)
-            .context("Failed to serialize disk info to JSON")?;
-        println!("{}", json);
+        println!(
+            "{}",
+            output::serialize(&serde_json::Value::from(disk_data), format, "disks")?
+        );
     } else {
         println!("{}", "=== Disk Information ===".bold().blue());
         for disk in &disks {
-            let total_gb = disk.total_space() / 1024 / 1024 / 1024;
-            let available_gb = disk.available_space() / 1024 / 1024 / 1024;
-            let used_gb = total_gb - available_gb;
-            let usage_percent = (used_gb as f64 / total_gb as f64) * 100.0;
+            let total = disk.total_space();
+            let available = disk.available_space();
+            let used = total.saturating_sub(available);
+            let usage_percent = (used as f64 / total as f64) * 100.0;
 
             println!("Device: {}", disk.name().to_string_lossy().green());
             println!("  Mount: {}", disk.mount_point().to_string_lossy().cyan());
@@ -480,9 +586,12 @@ fn show_disk_info(_sys: &sysinfo::System, format: &str) -> Result<()> {
                 "  Filesystem: {}",
                 disk.file_system().to_string_lossy().yellow()
             );
-            println!("  Total: {} GB", total_gb.to_string().bright_green());
-            println!("  Used: {} GB", used_gb.to_string().red());
-            println!("  Available: {} GB", available_gb.to_string().bright_blue());
+            println!("  Total: {}", format_size(total, units).bright_green());
+            println!("  Used: {}", format_size(used, units).red());
+            println!(
+                "  Available: {}",
+                format_size(available, units).bright_blue()
+            );
             println!("  Usage: {}%", format!("{:.1}", usage_percent).magenta());
 
             // Disk usage bar
@@ -501,19 +610,23 @@ fn show_disk_info(_sys: &sysinfo::System, format: &str) -> Result<()> {
     Ok(())
 }
 
-fn show_network_info(_sys: &sysinfo::System, format: &str) -> Result<()> {
+fn show_network_info(
+    _sys: &sysinfo::System,
+    format: OutputFormat,
+    units: &UnitsConfig,
+) -> Result<()> {
     use sysinfo::Networks;
     // Try to access network information through the new API
     let networks = Networks::new_with_refreshed_list();
 
-    if format == "json" {
+    if format != OutputFormat::Text {
         let network_data: Vec<serde_json::Value> = networks
             .iter()
             .map(|(name, data)| {
                 serde_json::json!({
                     "interface": name,
-                    "received_mb": data.received() / 1024 / 1024,
-                    "transmitted_mb": data.transmitted() / 1024 / 1024,
+                    "received_bytes": data.received(),
+                    "transmitted_bytes": data.transmitted(),
                     "packets_received": data.packets_received(),
                     "packets_transmitted": data.packets_transmitted(),
                     "errors_on_received": data.errors_on_received(),
@@ -522,22 +635,21 @@ fn show_network_info(_sys: &sysinfo::System, format: &str) -> Result<()> {
             })
             .collect();
 
-        let json = serde_json::to_string_pretty(&network_data)
-            .context("Failed to serialize network info to JSON")?;
-        println!("{}", json);
+        println!(
+            "{}",
+            output::serialize(&serde_json::Value::from(network_data), format, "interfaces")?
+        );
     } else {
         println!("{}", "=== Network Information ===".bold().blue());
         for (name, data) in &networks {
             println!("Interface: {}", name.green());
             println!(
-                "  Received: {} MB",
-                (data.received() / 1024 / 1024).to_string().bright_blue()
+                "  Received: {}",
+                format_size(data.received(), units).bright_blue()
             );
             println!(
-                "  Transmitted: {} MB",
-                (data.transmitted() / 1024 / 1024)
-                    .to_string()
-                    .bright_green()
+                "  Transmitted: {}",
+                format_size(data.transmitted(), units).bright_green()
             );
             println!(
                 "  Packets Received: {}",
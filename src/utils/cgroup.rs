@@ -0,0 +1,271 @@
+//! Resource limits of the cgroup rice runs in, so containers can report
+//! their share of the machine rather than the host's totals
+
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use std::path::{Path, PathBuf};
+
+#[cfg(target_os = "linux")]
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+/// cgroup v1 reports "no limit" as the largest page-aligned i64
+const UNLIMITED_V1: u64 = 1 << 62;
+
+/// Memory a cgroup may use and how much it uses, in bytes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryLimit {
+    pub limit: u64,
+    /// Usage without reclaimable page cache, as `docker stats` shows it
+    pub usage: u64,
+}
+
+/// Where a controller's files are for this process, from `/proc/self/cgroup`
+#[derive(Debug, Clone, PartialEq)]
+pub enum CgroupPath {
+    /// The unified hierarchy and the cgroup's path within it
+    V2(String),
+    /// A v1 hierarchy, mounted under the names of its controllers
+    V1 { controllers: String, path: String },
+}
+
+/// The memory limit of this process's cgroup or its closest limited parent
+#[cfg(target_os = "linux")]
+pub fn memory_limit() -> Option<MemoryLimit> {
+    memory_limit_in(&controller_dir("memory")?)
+}
+
+#[cfg(target_os = "linux")]
+fn memory_limit_in(controller: &ControllerDir) -> Option<MemoryLimit> {
+    let (limit_file, usage_file, inactive_key) = if controller.v2 {
+        ("memory.max", "memory.current", "inactive_file")
+    } else {
+        (
+            "memory.limit_in_bytes",
+            "memory.usage_in_bytes",
+            "total_inactive_file",
+        )
+    };
+
+    let limit = controller
+        .ancestors()
+        .filter_map(|dir| parse_limit(&fs::read_to_string(dir.join(limit_file)).ok()?))
+        .min()?;
+    let dir = &controller.dir;
+    let usage: u64 = read_number(&dir.join(usage_file))?;
+    let inactive = fs::read_to_string(dir.join("memory.stat"))
+        .ok()
+        .and_then(|stat| stat_value(&stat, inactive_key))
+        .unwrap_or(0);
+
+    Some(MemoryLimit {
+        limit,
+        usage: usage.saturating_sub(inactive),
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn memory_limit() -> Option<MemoryLimit> {
+    None
+}
+
+/// How many CPUs' worth of time the cgroup may use, e.g. 1.5
+#[cfg(target_os = "linux")]
+pub fn cpu_limit() -> Option<f64> {
+    cpu_limit_in(&controller_dir("cpu")?)
+}
+
+#[cfg(target_os = "linux")]
+fn cpu_limit_in(controller: &ControllerDir) -> Option<f64> {
+    controller
+        .ancestors()
+        .filter_map(|dir| {
+            if controller.v2 {
+                parse_cpu_max(&fs::read_to_string(dir.join("cpu.max")).ok()?)
+            } else {
+                let quota: i64 = read_number(&dir.join("cpu.cfs_quota_us"))?;
+                let period: i64 = read_number(&dir.join("cpu.cfs_period_us"))?;
+                (quota > 0 && period > 0).then(|| quota as f64 / period as f64)
+            }
+        })
+        .min_by(f64::total_cmp)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn cpu_limit() -> Option<f64> {
+    None
+}
+
+/// A controller's directory for this process within its hierarchy
+#[cfg(target_os = "linux")]
+struct ControllerDir {
+    /// Where the hierarchy is mounted
+    root: PathBuf,
+    dir: PathBuf,
+    v2: bool,
+}
+
+#[cfg(target_os = "linux")]
+impl ControllerDir {
+    /// The cgroup directory and its parents up to and including the
+    /// hierarchy's root, which holds the limits of a container's own cgroup
+    /// when it's mounted there (`0::/`)
+    fn ancestors(&self) -> impl Iterator<Item = &Path> {
+        self.dir
+            .ancestors()
+            .take_while(|dir| dir.starts_with(&self.root))
+    }
+}
+
+/// The directory holding a controller's files. Inside a container the
+/// host's path often isn't visible, and the container's own cgroup is
+/// mounted at the root instead.
+#[cfg(target_os = "linux")]
+fn controller_dir(controller: &str) -> Option<ControllerDir> {
+    let cgroups = fs::read_to_string("/proc/self/cgroup").ok()?;
+    let (root, path, v2) = match parse_proc_cgroup(&cgroups, controller)? {
+        CgroupPath::V2(path) => (PathBuf::from(CGROUP_ROOT), path, true),
+        CgroupPath::V1 { controllers, path } => {
+            (Path::new(CGROUP_ROOT).join(controllers), path, false)
+        }
+    };
+
+    let dir = root.join(path.trim_start_matches('/'));
+    let dir = if dir.is_dir() {
+        dir
+    } else if root.is_dir() {
+        root.clone()
+    } else {
+        return None;
+    };
+    Some(ControllerDir { root, dir, v2 })
+}
+
+#[cfg(target_os = "linux")]
+fn read_number<T: std::str::FromStr>(path: &Path) -> Option<T> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Find a controller in `/proc/self/cgroup`, whose lines are
+/// `hierarchy-id:controllers:path`. v1 hierarchies list their controllers,
+/// the v2 hierarchy has id 0 and none.
+pub fn parse_proc_cgroup(cgroups: &str, controller: &str) -> Option<CgroupPath> {
+    let mut unified = None;
+    for line in cgroups.lines() {
+        let mut fields = line.splitn(3, ':');
+        let (Some(id), Some(controllers), Some(path)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+
+        if id == "0" && controllers.is_empty() {
+            unified = Some(CgroupPath::V2(path.to_string()));
+        } else if controllers.split(',').any(|name| name == controller) {
+            return Some(CgroupPath::V1 {
+                controllers: controllers.to_string(),
+                path: path.to_string(),
+            });
+        }
+    }
+    unified
+}
+
+/// A memory limit in bytes, or `None` for "max" and v1's unlimited value
+pub fn parse_limit(limit: &str) -> Option<u64> {
+    let limit: u64 = limit.trim().parse().ok()?;
+    (limit < UNLIMITED_V1).then_some(limit)
+}
+
+/// `cpu.max` is "quota period" in microseconds, with "max" for no quota
+pub fn parse_cpu_max(cpu_max: &str) -> Option<f64> {
+    let mut fields = cpu_max.split_whitespace();
+    let quota: f64 = fields.next()?.parse().ok()?;
+    let period: f64 = fields.next()?.parse().ok()?;
+    (quota > 0.0 && period > 0.0).then(|| quota / period)
+}
+
+/// A value from a `memory.stat`-style file of "key value" lines
+pub fn stat_value(stat: &str, key: &str) -> Option<u64> {
+    stat.lines().find_map(|line| {
+        let (name, value) = line.split_once(' ')?;
+        (name == key).then(|| value.trim().parse().ok())?
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_cgroup() {
+        let hybrid = "4:memory:/docker/abc\n2:cpu,cpuacct:/docker/abc\n0::/system.slice\n";
+        assert_eq!(
+            parse_proc_cgroup(hybrid, "cpu"),
+            Some(CgroupPath::V1 {
+                controllers: "cpu,cpuacct".to_string(),
+                path: "/docker/abc".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_proc_cgroup(hybrid, "pids"),
+            Some(CgroupPath::V2("/system.slice".to_string()))
+        );
+        assert_eq!(
+            parse_proc_cgroup("0::/\n", "memory"),
+            Some(CgroupPath::V2("/".to_string()))
+        );
+        assert_eq!(parse_proc_cgroup("", "memory"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_limits_at_hierarchy_root() {
+        let root = std::env::temp_dir().join(format!("rice-cgroup-{}", std::process::id()));
+        let dir = root.join("user.slice");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(root.join("memory.max"), "1073741824\n").unwrap();
+        fs::write(root.join("cpu.max"), "200000 100000\n").unwrap();
+        fs::write(dir.join("memory.max"), "max\n").unwrap();
+        fs::write(dir.join("memory.current"), "600000000\n").unwrap();
+        fs::write(dir.join("memory.stat"), "inactive_file 100000000\n").unwrap();
+
+        // A container whose cgroup is the root itself (`0::/`)
+        let container = ControllerDir {
+            root: root.clone(),
+            dir: root.clone(),
+            v2: true,
+        };
+        assert_eq!(cpu_limit_in(&container), Some(2.0));
+
+        // A child cgroup inherits the root's limits
+        let child = ControllerDir {
+            root: root.clone(),
+            dir,
+            v2: true,
+        };
+        assert_eq!(
+            memory_limit_in(&child),
+            Some(MemoryLimit {
+                limit: 1073741824,
+                usage: 500000000,
+            })
+        );
+        assert_eq!(cpu_limit_in(&child), Some(2.0));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        assert_eq!(parse_limit("536870912\n"), Some(536870912));
+        assert_eq!(parse_limit("max\n"), None);
+        assert_eq!(parse_limit("9223372036854771712"), None);
+
+        assert_eq!(parse_cpu_max("150000 100000\n"), Some(1.5));
+        assert_eq!(parse_cpu_max("max 100000\n"), None);
+
+        let stat = "anon 1000\nfile 5000\ninactive_file 3000\nactive_file 2000\n";
+        assert_eq!(stat_value(stat, "inactive_file"), Some(3000));
+        assert_eq!(stat_value(stat, "total_inactive_file"), None);
+    }
+}
@@ -0,0 +1,109 @@
+//! Helpers for reading the configuration files and settings tools of
+//! terminals and desktop toolkits
+
+use std::path::PathBuf;
+use std::process::Command;
+
+/// `$XDG_CONFIG_HOME`, or `~/.config`, which terminals use on macOS too
+pub fn config_home() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+}
+
+/// Standard output of a command that ran successfully
+pub fn command_output(command: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(command).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// "Family Size", or just the family when no size is set
+pub fn font_with_size(family: &str, size: Option<&str>) -> Option<String> {
+    let family = family.trim();
+    if family.is_empty() {
+        return None;
+    }
+    match size.map(str::trim).filter(|size| !size.is_empty()) {
+        Some(size) => Some(format!("{} {}", family, size)),
+        None => Some(family.to_string()),
+    }
+}
+
+/// A value without the single or double quotes around it
+pub fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+/// Value of `key` in an INI-style file, within `section` or, for `None`,
+/// before the first section
+pub fn ini_value(contents: &str, section: Option<&str>, key: &str) -> Option<String> {
+    let mut current: Option<&str> = None;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            current = Some(name);
+            continue;
+        }
+        if current != section {
+            continue;
+        }
+        if let Some((name, value)) = line.split_once('=') {
+            if name.trim() == key {
+                return Some(unquote(value).to_string());
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ini_value() {
+        let contents = "top = level\n[Appearance]\n# style=Nope\nstyle = \"Breeze\"\n\
+                        [Fonts]\nstyle='Fusion'\n";
+        assert_eq!(ini_value(contents, None, "top").as_deref(), Some("level"));
+        assert_eq!(
+            ini_value(contents, Some("Appearance"), "style").as_deref(),
+            Some("Breeze")
+        );
+        assert_eq!(
+            ini_value(contents, Some("Fonts"), "style").as_deref(),
+            Some("Fusion")
+        );
+        assert_eq!(ini_value(contents, Some("Icons"), "style"), None);
+    }
+
+    #[test]
+    fn test_font_with_size() {
+        assert_eq!(
+            font_with_size(" Hack ", Some("11")).as_deref(),
+            Some("Hack 11")
+        );
+        assert_eq!(font_with_size("Hack", Some(" ")).as_deref(), Some("Hack"));
+        assert_eq!(font_with_size("", Some("11")), None);
+        assert_eq!(unquote(" 'Fira Code' "), "Fira Code");
+    }
+}
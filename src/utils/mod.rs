@@ -1 +1,8 @@
+pub mod cgroup;
+pub mod config_files;
+pub mod process;
 pub mod terminal;
+pub mod time;
+pub mod units;
+#[cfg(all(unix, not(target_os = "macos")))]
+pub mod x11;
@@ -0,0 +1,173 @@
+use std::path::PathBuf;
+
+/// Ancestors are walked at most this far, in case of a cycle
+const MAX_DEPTH: usize = 64;
+/// Programs that run the script named by their first argument
+const INTERPRETERS: &[&str] = &["python", "perl", "node"];
+
+/// A running process, as far as rice needs to know it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Process {
+    pub pid: u32,
+    pub ppid: u32,
+    /// Short command name, at most 15 characters on Linux
+    pub name: String,
+    pub exe: Option<PathBuf>,
+    pub cmdline: Vec<String>,
+}
+
+impl Process {
+    /// Name of the program, preferring the executable over the truncated
+    /// command name, e.g. `gnome-terminal-server` rather than `gnome-terminal-`,
+    /// and looking past interpreters such as `python3 /usr/bin/terminator`
+    pub fn program(&self) -> String {
+        let program = self
+            .exe
+            .as_ref()
+            .and_then(|exe| exe.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.name.clone());
+        // Login shells are named e.g. `-zsh`
+        let program = program.trim_start_matches('-');
+
+        let interpreted = INTERPRETERS
+            .iter()
+            .any(|interpreter| program.starts_with(interpreter));
+        if interpreted {
+            if let Some(script) = self
+                .cmdline
+                .iter()
+                .skip(1)
+                .find(|arg| !arg.starts_with('-'))
+            {
+                return script.rsplit('/').next().unwrap_or(script).to_string();
+            }
+        }
+
+        program.to_string()
+    }
+}
+
+/// The parent, grandparent, ... of a process, stopping before init
+pub fn ancestors(pid: u32) -> impl Iterator<Item = Process> {
+    let mut next = read_process(pid).map(|process| process.ppid);
+
+    std::iter::from_fn(move || {
+        let pid = next.filter(|&pid| pid > 1)?;
+        let process = read_process(pid)?;
+        next = Some(process.ppid);
+        Some(process)
+    })
+    .take(MAX_DEPTH)
+}
+
+#[cfg(target_os = "linux")]
+pub fn read_process(pid: u32) -> Option<Process> {
+    use std::fs;
+
+    let dir = PathBuf::from(format!("/proc/{}", pid));
+    let (name, ppid) = parse_stat(&fs::read_to_string(dir.join("stat")).ok()?)?;
+
+    let name = fs::read_to_string(dir.join("comm"))
+        .map(|comm| comm.trim_end_matches('\n').to_string())
+        .unwrap_or(name);
+    // Links to the executable can't be read for other users' processes
+    let exe = fs::read_link(dir.join("exe")).ok();
+    let cmdline = fs::read(dir.join("cmdline"))
+        .unwrap_or_default()
+        .split(|&b| b == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).to_string())
+        .collect();
+
+    Some(Process {
+        pid,
+        ppid,
+        name,
+        exe,
+        cmdline,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_process(pid: u32) -> Option<Process> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+    let pid = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        false,
+        ProcessRefreshKind::nothing()
+            .with_exe(UpdateKind::OnlyIfNotSet)
+            .with_cmd(UpdateKind::OnlyIfNotSet),
+    );
+    let process = sys.process(pid)?;
+
+    Some(Process {
+        pid: pid.as_u32(),
+        ppid: process.parent().map(|parent| parent.as_u32()).unwrap_or(0),
+        name: process.name().to_string_lossy().to_string(),
+        exe: process.exe().map(PathBuf::from),
+        cmdline: process
+            .cmd()
+            .iter()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect(),
+    })
+}
+
+/// Command name and parent pid from `/proc/<pid>/stat`. The name is in
+/// parentheses and may itself contain spaces and parentheses.
+pub fn parse_stat(stat: &str) -> Option<(String, u32)> {
+    let open = stat.find('(')?;
+    let close = stat.rfind(')')?;
+    let name = stat.get(open + 1..close)?.to_string();
+
+    // After the name: state, then ppid
+    let ppid = stat[close + 1..].split_whitespace().nth(1)?.parse().ok()?;
+    Some((name, ppid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stat() {
+        assert_eq!(
+            parse_stat("4242 (bash) S 4200 4242 4242 34816 0"),
+            Some(("bash".to_string(), 4200))
+        );
+        assert_eq!(
+            parse_stat("77 (tmux: server) S 1 77 77 0 -1"),
+            Some(("tmux: server".to_string(), 1))
+        );
+        assert_eq!(
+            parse_stat("9 (a) b) (c) R 8 9 9 0"),
+            Some(("a) b) (c".to_string(), 8))
+        );
+        assert_eq!(parse_stat("garbage"), None);
+    }
+
+    #[test]
+    fn test_program() {
+        let process = |name: &str, cmdline: &[&str]| Process {
+            pid: 2,
+            ppid: 1,
+            name: name.to_string(),
+            exe: None,
+            cmdline: cmdline.iter().map(|arg| arg.to_string()).collect(),
+        };
+
+        assert_eq!(process("-zsh", &["-zsh"]).program(), "zsh");
+        assert_eq!(
+            process(
+                "python3",
+                &["/usr/bin/python3", "-O", "/usr/bin/terminator"]
+            )
+            .program(),
+            "terminator"
+        );
+    }
+}
@@ -1 +1,140 @@
-// This file is kept for future terminal capability detection but currently unused
+use clap::ValueEnum;
+use std::env;
+use std::io::IsTerminal;
+use unicode_width::UnicodeWidthStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ColorMode {
+    /// Color when stdout is a terminal, honoring NO_COLOR and CLICOLOR_FORCE
+    Auto,
+    Always,
+    Never,
+}
+
+/// Decide whether to emit ANSI colors and apply it to all colored output
+pub fn init_colors(mode: ColorMode) -> bool {
+    let enabled = color_preference(mode).unwrap_or_else(|| std::io::stdout().is_terminal());
+    colored::control::set_override(enabled);
+    enabled
+}
+
+/// Colors asked for or against through `--color`, NO_COLOR or CLICOLOR(_FORCE),
+/// or `None` when it's up to whether the output is a terminal
+pub fn color_preference(mode: ColorMode) -> Option<bool> {
+    match mode {
+        ColorMode::Always => Some(true),
+        ColorMode::Never => Some(false),
+        ColorMode::Auto => {
+            let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+
+            if var("NO_COLOR").is_some() {
+                Some(false)
+            } else if var("CLICOLOR_FORCE").is_some_and(|value| value != "0") {
+                Some(true)
+            } else if var("CLICOLOR").is_some_and(|value| value == "0") {
+                Some(false)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Whether colors and other escape sequences should be written
+pub fn colors_enabled() -> bool {
+    colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
+/// Remove ANSI escape sequences (CSI and OSC) from a string
+pub fn strip_ansi(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            output.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            // CSI: ESC [ params final-byte
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: ESC ] ... BEL or ESC \
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' || (c == '\x1b' && chars.peek() == Some(&'\\')) {
+                        if c == '\x1b' {
+                            chars.next();
+                        }
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    output
+}
+
+/// Number of terminal columns a string occupies, ignoring ANSI escapes
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(strip_ansi(text).as_str())
+}
+
+/// Size of the terminal attached to stdout as (columns, rows)
+#[cfg(unix)]
+pub fn terminal_size() -> Option<(u16, u16)> {
+    // SAFETY: TIOCGWINSZ only writes into the zeroed winsize struct we pass
+    let size = unsafe {
+        let mut size: libc::winsize = std::mem::zeroed();
+        if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) != 0 {
+            return None;
+        }
+        size
+    };
+
+    (size.ws_col > 0 && size.ws_row > 0).then_some((size.ws_col, size.ws_row))
+}
+
+#[cfg(not(unix))]
+pub fn terminal_size() -> Option<(u16, u16)> {
+    let var = |name: &str| env::var(name).ok()?.parse().ok();
+    Some((var("COLUMNS")?, var("LINES")?))
+}
+
+/// Whether the terminal can be expected to render icon glyphs and emoji
+pub fn supports_icons() -> bool {
+    supports_icons_with(|name| env::var(name).ok())
+}
+
+/// `supports_icons` reading environment variables through `var`
+pub fn supports_icons_with(var: impl Fn(&str) -> Option<String>) -> bool {
+    let term = var("TERM").unwrap_or_default();
+
+    // The Linux virtual console and dumb terminals only have basic glyphs
+    if term == "linux" || term == "dumb" {
+        return false;
+    }
+
+    if cfg!(any(windows, target_os = "macos")) {
+        return true;
+    }
+
+    // Otherwise require a UTF-8 locale, checked in POSIX precedence order
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| var(name))
+        .find(|value| !value.is_empty())
+        .map(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+        .unwrap_or(false)
+}
@@ -0,0 +1,127 @@
+//! Local time formatting through the C library, which knows the system's
+//! time zone database and the user's locale
+
+/// Format a Unix timestamp in local time with a strftime format, e.g.
+/// "%Y-%m-%d %H:%M". Returns `None` if the result is empty.
+#[cfg(unix)]
+pub fn format_local(timestamp: i64, format: &str) -> Option<String> {
+    use_time_locale();
+    let format = std::ffi::CString::new(format).ok()?;
+    let tm = local_tm(timestamp)?;
+
+    // strftime returns 0 both when the buffer is too small and when the
+    // output is empty, so grow the buffer a few times before giving up
+    let mut size = 256;
+    while size <= 64 * 1024 {
+        let mut buffer = vec![0u8; size];
+        // SAFETY: strftime writes at most buffer.len() bytes, including the
+        // terminating NUL, and returns how many it wrote before the NUL
+        let length = unsafe {
+            libc::strftime(
+                buffer.as_mut_ptr().cast(),
+                buffer.len(),
+                format.as_ptr(),
+                &tm,
+            )
+        };
+        if length > 0 {
+            buffer.truncate(length);
+            return Some(String::from_utf8_lossy(&buffer).to_string());
+        }
+        size *= 4;
+    }
+    None
+}
+
+/// Switch strftime from the C locale to the user's `LC_TIME`, so names of
+/// days and months and formats like `%c` follow it
+#[cfg(unix)]
+fn use_time_locale() {
+    static INIT: std::sync::Once = std::sync::Once::new();
+    // SAFETY: an empty string selects the locale from the environment, and
+    // only time formatting is affected. Once keeps threads from racing.
+    INIT.call_once(|| unsafe {
+        libc::setlocale(libc::LC_TIME, c"".as_ptr());
+    });
+}
+
+#[cfg(not(unix))]
+pub fn format_local(_timestamp: i64, _format: &str) -> Option<String> {
+    None
+}
+
+/// Abbreviation and UTC offset in seconds of the local time zone at a
+/// timestamp, e.g. ("CEST", 7200)
+#[cfg(unix)]
+pub fn zone_at(timestamp: i64) -> Option<(String, i64)> {
+    let tm = local_tm(timestamp)?;
+    let abbreviation = if tm.tm_zone.is_null() {
+        String::new()
+    } else {
+        // SAFETY: tm_zone points to a static NUL-terminated string owned by libc
+        unsafe { std::ffi::CStr::from_ptr(tm.tm_zone) }
+            .to_string_lossy()
+            .to_string()
+    };
+    // c_long is 32 bits wide on 32-bit targets
+    #[allow(clippy::unnecessary_cast)]
+    let offset = tm.tm_gmtoff as i64;
+    Some((abbreviation, offset))
+}
+
+#[cfg(not(unix))]
+pub fn zone_at(_timestamp: i64) -> Option<(String, i64)> {
+    None
+}
+
+#[cfg(unix)]
+fn local_tm(timestamp: i64) -> Option<libc::tm> {
+    let time = timestamp as libc::time_t;
+    // SAFETY: localtime_r only writes into the zeroed tm struct we pass
+    unsafe {
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&time, &mut tm).is_null() {
+            return None;
+        }
+        Some(tm)
+    }
+}
+
+/// The current Unix time in seconds
+pub fn now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+/// "UTC+02:00", "UTC-03:30" or "UTC"
+pub fn format_offset(seconds: i64) -> String {
+    if seconds == 0 {
+        return "UTC".to_string();
+    }
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.abs() / 60;
+    format!("UTC{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_offset() {
+        assert_eq!(format_offset(0), "UTC");
+        assert_eq!(format_offset(7200), "UTC+02:00");
+        assert_eq!(format_offset(-12600), "UTC-03:30");
+        assert_eq!(format_offset(20700), "UTC+05:45");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_format_local_long_output() {
+        let format = "%Y".repeat(200);
+        let formatted = format_local(0, &format).unwrap();
+        assert_eq!(formatted.len(), 800);
+        assert_eq!(format_local(0, ""), None);
+    }
+}
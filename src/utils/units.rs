@@ -0,0 +1,133 @@
+use crate::config::{Unit, UnitSystem, UnitsConfig};
+
+/// Units picked for each system, smallest first
+const IEC_UNITS: &[Unit] = &[
+    Unit::Byte,
+    Unit::Kibibyte,
+    Unit::Mebibyte,
+    Unit::Gibibyte,
+    Unit::Tebibyte,
+    Unit::Pebibyte,
+];
+const SI_UNITS: &[Unit] = &[
+    Unit::Byte,
+    Unit::Kilobyte,
+    Unit::Megabyte,
+    Unit::Gigabyte,
+    Unit::Terabyte,
+    Unit::Petabyte,
+];
+
+impl Unit {
+    /// Size of one unit in bytes
+    pub fn bytes(self) -> u64 {
+        match self {
+            Unit::Byte => 1,
+            Unit::Kibibyte => 1 << 10,
+            Unit::Mebibyte => 1 << 20,
+            Unit::Gibibyte => 1 << 30,
+            Unit::Tebibyte => 1 << 40,
+            Unit::Pebibyte => 1 << 50,
+            Unit::Kilobyte => 1_000,
+            Unit::Megabyte => 1_000_000,
+            Unit::Gigabyte => 1_000_000_000,
+            Unit::Terabyte => 1_000_000_000_000,
+            Unit::Petabyte => 1_000_000_000_000_000,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Unit::Byte => "B",
+            Unit::Kibibyte => "KiB",
+            Unit::Mebibyte => "MiB",
+            Unit::Gibibyte => "GiB",
+            Unit::Tebibyte => "TiB",
+            Unit::Pebibyte => "PiB",
+            Unit::Kilobyte => "kB",
+            Unit::Megabyte => "MB",
+            Unit::Gigabyte => "GB",
+            Unit::Terabyte => "TB",
+            Unit::Petabyte => "PB",
+        }
+    }
+}
+
+/// Format a byte count with the configured unit system and precision,
+/// e.g. "5.9 GiB". Whole bytes are shown without decimals.
+pub fn format_size(bytes: u64, config: &UnitsConfig) -> String {
+    let unit = config
+        .unit
+        .unwrap_or_else(|| pick_unit(bytes, config.system, config.precision));
+
+    if unit == Unit::Byte {
+        format!("{} B", bytes)
+    } else {
+        format!(
+            "{:.*} {}",
+            config.precision,
+            bytes as f64 / unit.bytes() as f64,
+            unit.symbol()
+        )
+    }
+}
+
+/// The largest unit that keeps the value at or above 1, moving up a unit when
+/// rounding would show e.g. "1024.0 KiB"
+fn pick_unit(bytes: u64, system: UnitSystem, precision: usize) -> Unit {
+    let units = match system {
+        UnitSystem::Iec => IEC_UNITS,
+        UnitSystem::Si => SI_UNITS,
+    };
+    let scale = 10f64.powi(precision.min(15) as i32);
+
+    let mut picked = units[0];
+    for (i, &unit) in units.iter().enumerate() {
+        if bytes < unit.bytes() {
+            break;
+        }
+        picked = unit;
+
+        let value = bytes as f64 / unit.bytes() as f64;
+        let next = units.get(i + 1);
+        if let Some(&next) = next {
+            let step = (next.bytes() / unit.bytes()) as f64;
+            if (value * scale).round() / scale >= step {
+                picked = next;
+            }
+        }
+    }
+    picked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn units(system: UnitSystem, precision: usize, unit: Option<Unit>) -> UnitsConfig {
+        UnitsConfig {
+            system,
+            precision,
+            unit,
+        }
+    }
+
+    #[test]
+    fn test_format_size() {
+        let iec = UnitsConfig::default();
+        assert_eq!(format_size(0, &iec), "0 B");
+        assert_eq!(format_size(1023, &iec), "1023 B");
+        assert_eq!(format_size(1536, &iec), "1.5 KiB");
+        assert_eq!(format_size(6_294_216_704, &iec), "5.9 GiB");
+        assert_eq!(format_size(1_048_575, &iec), "1.0 MiB");
+
+        let si = units(UnitSystem::Si, 2, None);
+        assert_eq!(format_size(999, &si), "999 B");
+        assert_eq!(format_size(6_294_216_704, &si), "6.29 GB");
+        assert_eq!(format_size(1_500_000_000_000, &si), "1.50 TB");
+
+        let fixed = units(UnitSystem::Iec, 0, Some(Unit::Mebibyte));
+        assert_eq!(format_size(6_294_216_704, &fixed), "6003 MiB");
+        assert_eq!(format_size(512, &fixed), "0 MiB");
+    }
+}
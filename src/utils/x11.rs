@@ -0,0 +1,385 @@
+//! Just enough of the X11 wire protocol to read window properties, so window
+//! managers can be identified without xprop or libX11.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{IpAddr, TcpStream, ToSocketAddrs};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_millis(500);
+const AUTH_PROTOCOL: &str = "MIT-MAGIC-COOKIE-1";
+
+/// Xauthority address families
+const FAMILY_INTERNET: u16 = 0;
+const FAMILY_INTERNET6: u16 = 6;
+const FAMILY_LOCAL: u16 = 256;
+const FAMILY_WILD: u16 = 65535;
+
+const OPCODE_INTERN_ATOM: u8 = 16;
+const OPCODE_GET_PROPERTY: u8 = 20;
+
+trait Stream: Read + Write {}
+impl<T: Read + Write> Stream for T {}
+
+/// A connection to an X server
+pub struct Connection {
+    stream: Box<dyn Stream>,
+    root: u32,
+}
+
+/// Connect to a remote display, trying each of the host's addresses in turn
+/// so an unreachable host can't hang the caller
+fn connect_tcp(host: &str, port: u16) -> Result<TcpStream> {
+    let mut error = None;
+    for address in (host, port)
+        .to_socket_addrs()
+        .with_context(|| format!("Failed to resolve {}", host))?
+    {
+        match TcpStream::connect_timeout(&address, TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => error = Some(e),
+        }
+    }
+    match error {
+        Some(e) => Err(e).with_context(|| format!("Failed to connect to {}:{}", host, port)),
+        None => bail!("No addresses found for {}", host),
+    }
+}
+
+/// Host and display number from `$DISPLAY`, e.g. `(None, 0)` for ":0.0"
+/// and `(Some("remote"), 10)` for "remote:10"
+pub fn parse_display(display: &str) -> Option<(Option<String>, u32)> {
+    let (host, rest) = display.rsplit_once(':')?;
+    let number = rest.split('.').next()?.parse().ok()?;
+    // "unix:0" and paths such as "/tmp/launch-x/org.xquartz:0" are local
+    let host =
+        Some(host).filter(|host| !host.is_empty() && *host != "unix" && !host.starts_with('/'));
+    Some((host.map(str::to_string), number))
+}
+
+impl Connection {
+    /// Connect to the display in `$DISPLAY`
+    pub fn connect_env() -> Result<Self> {
+        let display = std::env::var("DISPLAY").context("DISPLAY is not set")?;
+        Self::connect(&display)
+    }
+
+    pub fn connect(display: &str) -> Result<Self> {
+        let (host, number) =
+            parse_display(display).with_context(|| format!("Invalid display '{}'", display))?;
+
+        let (stream, peer): (Box<dyn Stream>, _) = match &host {
+            Some(host) => {
+                let port = u16::try_from(6000 + u64::from(number))
+                    .with_context(|| format!("Invalid display number in '{}'", display))?;
+                let stream = connect_tcp(host, port)?;
+                stream.set_read_timeout(Some(TIMEOUT))?;
+                stream.set_write_timeout(Some(TIMEOUT))?;
+                let peer = stream.peer_addr()?.ip();
+                (Box::new(stream), Some(peer))
+            }
+            None => {
+                let stream = connect_local(number)?;
+                stream.set_read_timeout(Some(TIMEOUT))?;
+                stream.set_write_timeout(Some(TIMEOUT))?;
+                (Box::new(stream), None)
+            }
+        };
+
+        let mut connection = Self { stream, root: 0 };
+        connection.setup(peer, number)?;
+        Ok(connection)
+    }
+
+    /// The root window of the first screen
+    pub fn root(&self) -> u32 {
+        self.root
+    }
+
+    fn setup(&mut self, peer: Option<IpAddr>, number: u32) -> Result<()> {
+        let cookie = read_cookie(peer, number).unwrap_or_default();
+        let name: &[u8] = if cookie.is_empty() {
+            &[]
+        } else {
+            AUTH_PROTOCOL.as_bytes()
+        };
+
+        // Little-endian byte order, protocol version 11.0
+        let mut request = vec![b'l', 0];
+        request.extend(11u16.to_le_bytes());
+        request.extend(0u16.to_le_bytes());
+        request.extend((name.len() as u16).to_le_bytes());
+        request.extend((cookie.len() as u16).to_le_bytes());
+        request.extend([0, 0]);
+        push_padded(&mut request, name);
+        push_padded(&mut request, &cookie);
+        self.stream.write_all(&request)?;
+
+        let mut header = [0u8; 8];
+        self.stream.read_exact(&mut header)?;
+        let mut data = vec![0u8; u16_at(&header, 6) as usize * 4];
+        self.stream.read_exact(&mut data)?;
+
+        if header[0] != 1 {
+            let reason_length = (header[1] as usize).min(data.len());
+            bail!(
+                "X server refused the connection: {}",
+                String::from_utf8_lossy(&data[..reason_length])
+            );
+        }
+
+        // The first screen follows the vendor string and pixmap formats
+        let vendor_length = u16_at(&data, 16) as usize;
+        let formats = *data.get(21).context("Truncated X setup reply")? as usize;
+        let screen = 32 + pad(vendor_length) + formats * 8;
+        self.root = u32_at(&data, screen).context("Truncated X setup reply")?;
+        Ok(())
+    }
+
+    /// The atom for `name`, or `None` if no client has created it
+    pub fn intern_atom(&mut self, name: &str) -> Result<Option<u32>> {
+        let mut request = vec![OPCODE_INTERN_ATOM, 1];
+        request.extend(((8 + pad(name.len())) as u16 / 4).to_le_bytes());
+        request.extend((name.len() as u16).to_le_bytes());
+        request.extend([0, 0]);
+        push_padded(&mut request, name.as_bytes());
+
+        let reply = self.request(&request)?;
+        Ok(u32_at(&reply, 8).filter(|&atom| atom != 0))
+    }
+
+    /// The value of a window property, or `None` if it isn't set
+    pub fn get_property(&mut self, window: u32, property: u32) -> Result<Option<Vec<u8>>> {
+        let mut request = vec![OPCODE_GET_PROPERTY, 0];
+        request.extend(6u16.to_le_bytes());
+        request.extend(window.to_le_bytes());
+        request.extend(property.to_le_bytes());
+        // Any type, from the start, up to 4 KiB
+        request.extend(0u32.to_le_bytes());
+        request.extend(0u32.to_le_bytes());
+        request.extend(1024u32.to_le_bytes());
+
+        let reply = self.request(&request)?;
+        let format = reply[1] as usize;
+        if format == 0 {
+            return Ok(None);
+        }
+        let length = u32_at(&reply, 16).unwrap_or(0) as usize * format / 8;
+        Ok(reply.get(32..32 + length).map(<[u8]>::to_vec))
+    }
+
+    /// A window property holding a single window, such as `_NET_SUPPORTING_WM_CHECK`
+    pub fn get_window_property(&mut self, window: u32, property: &str) -> Result<Option<u32>> {
+        let Some(atom) = self.intern_atom(property)? else {
+            return Ok(None);
+        };
+        Ok(self
+            .get_property(window, atom)?
+            .and_then(|value| u32_at(&value, 0)))
+    }
+
+    /// A text window property such as `_NET_WM_NAME`
+    pub fn get_string_property(&mut self, window: u32, property: &str) -> Result<Option<String>> {
+        let Some(atom) = self.intern_atom(property)? else {
+            return Ok(None);
+        };
+        Ok(self.get_property(window, atom)?.map(|value| {
+            String::from_utf8_lossy(&value)
+                .trim_end_matches('\0')
+                .to_string()
+        }))
+    }
+
+    /// Send a request and read its reply, failing on X errors
+    fn request(&mut self, request: &[u8]) -> Result<Vec<u8>> {
+        self.stream.write_all(request)?;
+
+        loop {
+            let mut reply = vec![0u8; 32];
+            self.stream.read_exact(&mut reply)?;
+            match reply[0] {
+                0 => bail!("X error {}", reply[1]),
+                1 => {
+                    let extra = u32_at(&reply, 4).unwrap_or(0) as usize * 4;
+                    let mut data = vec![0u8; extra];
+                    self.stream.read_exact(&mut data)?;
+                    reply.extend(data);
+                    return Ok(reply);
+                }
+                // Events aren't selected, but skip any that arrive
+                _ => continue,
+            }
+        }
+    }
+}
+
+/// Connect to the local socket for a display, trying the Linux abstract
+/// socket that some servers only listen on
+fn connect_local(number: u32) -> Result<UnixStream> {
+    let path = format!("/tmp/.X11-unix/X{}", number);
+    match UnixStream::connect(&path) {
+        Ok(stream) => Ok(stream),
+        #[cfg(target_os = "linux")]
+        Err(_) => {
+            use std::os::linux::net::SocketAddrExt;
+            use std::os::unix::net::SocketAddr;
+
+            let address = SocketAddr::from_abstract_name(path.as_bytes())?;
+            Ok(UnixStream::connect_addr(&address)?)
+        }
+        #[cfg(not(target_os = "linux"))]
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The MIT-MAGIC-COOKIE-1 for a display from the Xauthority file. `peer` is
+/// the server's address for TCP connections and `None` for local sockets.
+fn read_cookie(peer: Option<IpAddr>, number: u32) -> Option<Vec<u8>> {
+    let path = std::env::var_os("XAUTHORITY")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".Xauthority")))?;
+    let xauthority = fs::read(path).ok()?;
+    let number = number.to_string();
+
+    match peer {
+        Some(IpAddr::V4(ip)) if !ip.is_loopback() => {
+            find_cookie(&xauthority, FAMILY_INTERNET, &ip.octets(), &number)
+        }
+        Some(IpAddr::V6(ip)) if !ip.is_loopback() => {
+            find_cookie(&xauthority, FAMILY_INTERNET6, &ip.octets(), &number)
+        }
+        // Like libX11, loopback connections (e.g. SSH forwarding) use the
+        // entries for this machine's host name
+        _ => {
+            let hostname = fs::read_to_string("/etc/hostname")
+                .ok()
+                .map(|name| name.trim().to_string())
+                .or_else(sysinfo::System::host_name)
+                .unwrap_or_default();
+            find_cookie(&xauthority, FAMILY_LOCAL, hostname.as_bytes(), &number)
+        }
+    }
+}
+
+/// Xauthority entries are a family followed by length-prefixed address,
+/// display number, auth name and auth data. Internet entries hold the raw
+/// address bytes and local entries the host name.
+pub fn find_cookie(
+    xauthority: &[u8],
+    family: u16,
+    address: &[u8],
+    number: &str,
+) -> Option<Vec<u8>> {
+    let mut rest = xauthority;
+    let mut take = |length: usize| -> Option<&[u8]> {
+        let (field, remaining) = (rest.get(..length)?, rest.get(length..)?);
+        rest = remaining;
+        Some(field)
+    };
+
+    loop {
+        let entry_family = u16::from_be_bytes(take(2)?.try_into().ok()?);
+        let mut fields = Vec::with_capacity(4);
+        for _ in 0..4 {
+            let length = u16::from_be_bytes(take(2)?.try_into().ok()?) as usize;
+            fields.push(take(length)?);
+        }
+        let (entry_address, display, name, data) = (fields[0], fields[1], fields[2], fields[3]);
+
+        let host_matches =
+            entry_family == FAMILY_WILD || (entry_family == family && entry_address == address);
+        let display_matches = display.is_empty() || display == number.as_bytes();
+        if host_matches && display_matches && name == AUTH_PROTOCOL.as_bytes() {
+            return Some(data.to_vec());
+        }
+    }
+}
+
+fn pad(length: usize) -> usize {
+    length.div_ceil(4) * 4
+}
+
+fn push_padded(buffer: &mut Vec<u8>, data: &[u8]) {
+    buffer.extend(data);
+    buffer.resize(buffer.len() + pad(data.len()) - data.len(), 0);
+}
+
+fn u16_at(data: &[u8], offset: usize) -> u16 {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .unwrap_or(0)
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_display() {
+        assert_eq!(parse_display(":0"), Some((None, 0)));
+        assert_eq!(parse_display(":1.0"), Some((None, 1)));
+        assert_eq!(parse_display("unix:2"), Some((None, 2)));
+        assert_eq!(
+            parse_display("remote:10.0"),
+            Some((Some("remote".to_string()), 10))
+        );
+        assert_eq!(
+            parse_display("/tmp/launch-abc/org.xquartz:0"),
+            Some((None, 0))
+        );
+        assert_eq!(parse_display("nonsense"), None);
+    }
+
+    #[test]
+    fn test_find_cookie() {
+        let entry = |family: u16, address: &[u8], number: &str, data: &[u8]| {
+            let mut entry = family.to_be_bytes().to_vec();
+            for field in [address, number.as_bytes(), AUTH_PROTOCOL.as_bytes(), data] {
+                entry.extend((field.len() as u16).to_be_bytes());
+                entry.extend(field);
+            }
+            entry
+        };
+
+        let mut xauthority = entry(FAMILY_LOCAL, b"otherhost", "0", &[1, 1]);
+        xauthority.extend(entry(FAMILY_LOCAL, b"myhost", "1", &[2, 2]));
+        xauthority.extend(entry(FAMILY_LOCAL, b"myhost", "0", &[3, 3]));
+        xauthority.extend(entry(FAMILY_INTERNET, &[192, 168, 1, 5], "10", &[5, 5]));
+
+        let local =
+            |host: &str, number| find_cookie(&xauthority, FAMILY_LOCAL, host.as_bytes(), number);
+        assert_eq!(local("myhost", "0"), Some(vec![3, 3]));
+        assert_eq!(local("myhost", "1"), Some(vec![2, 2]));
+        assert_eq!(local("myhost", "2"), None);
+
+        // Internet entries match the address bytes, not the host name
+        let internet = |address: &[u8]| find_cookie(&xauthority, FAMILY_INTERNET, address, "10");
+        assert_eq!(internet(&[192, 168, 1, 5]), Some(vec![5, 5]));
+        assert_eq!(internet(&[192, 168, 1, 6]), None);
+        assert_eq!(local("192.168.1.5", "10"), None);
+
+        assert_eq!(
+            find_cookie(
+                &entry(FAMILY_WILD, b"", "", &[4]),
+                FAMILY_LOCAL,
+                b"myhost",
+                "0"
+            ),
+            Some(vec![4])
+        );
+    }
+
+    #[test]
+    fn test_display_number_out_of_port_range() {
+        let error = Connection::connect("remote:70000").err().unwrap();
+        assert!(error.to_string().contains("Invalid display number"));
+    }
+}